@@ -13,12 +13,12 @@ fn main() {
 
     let mut input = File::open(main).unwrap();
     let mut input_content = vec![];
-    input.read_to_end(&mut input_content).expect(&format!("Failed to read {}", &main));
+    input.read_to_end(&mut input_content).unwrap_or_else(|_| panic!("Failed to read {}", &main));
     let content = String::from_utf8(input_content).unwrap();
 
     let crate_re = Regex::new(r"extern crate [a-zA-Z_0-9]+;").unwrap();
     let out_content = crate_re.replace_all(&content, "// Crate definition was here!");
 
     let mut output = File::create(compiled).unwrap();
-    output.write_all(&out_content.as_bytes()).expect(&format!("Failed to write into {}", &compiled));
+    output.write_all(out_content.as_bytes()).unwrap_or_else(|_| panic!("Failed to write into {}", &compiled));
 }
\ No newline at end of file
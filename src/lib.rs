@@ -1,2 +1,2 @@
 #[macro_use]
-mod shared;
\ No newline at end of file
+pub mod shared;
\ No newline at end of file
@@ -1,10 +1,19 @@
 #[macro_use]
 extern crate olympic_lib;
 
-fn main() {
-    readln!(x: i64);
+use std::io::{BufRead, Write};
+use olympic_lib::shared::integers::ilog2;
+use olympic_lib::shared::io::run;
+
+fn solve<R: BufRead, W: Write>(input: &mut R, output: &mut W) {
+    readln!(input; x: i64);
     for _ in 0..x {
-        readln!(y: i64);
-        println!("{}", y * (y + 1) / 2 - 2_i64.pow(((y as f64).log2() as u32) + 2) + 2);
+        readln!(input; y: i64);
+        assert!(y >= 1, "Numbers must be positive");
+        writeln!(output, "{}", y * (y + 1) / 2 - 2_i64.pow(ilog2(y) + 2) + 2).unwrap();
     }
-}
\ No newline at end of file
+}
+
+fn main() {
+    run(solve);
+}
@@ -1,6 +1,12 @@
-const LOW_BITS: u64 = ((1 as u64) << 32) - 1;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Error};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use std::str::FromStr;
+use shared::ntt::convolution_exact;
+
+const LOW_BITS: u64 = (1_u64 << 32) - 1;
 const HIGH_BITS: u64 = LOW_BITS << 32;
-fn big_prod(first: u64, second: u64) -> (u64, u64) {
+pub fn big_prod(first: u64, second: u64) -> (u64, u64) {
     let flow:   u64 = first & LOW_BITS;
     let slow:   u64 = second & LOW_BITS;
     let fhigh:  u64 = (first & HIGH_BITS) >> 32;
@@ -11,4 +17,705 @@ fn big_prod(first: u64, second: u64) -> (u64, u64) {
     let secdig: u64 = (resm1 & LOW_BITS) + (resm2 & LOW_BITS) + ((reslow & HIGH_BITS) >> 32);
     (((secdig & HIGH_BITS) >> 32) + ((resm1 & HIGH_BITS) >> 32) + ((resm2 & HIGH_BITS) >> 32) + fhigh * shigh,
      ((secdig & LOW_BITS) << 32) | (reslow & LOW_BITS))
-}
\ No newline at end of file
+}
+
+/// Largest power of ten in a limb, and its exponent.
+const DECIMAL_BASE: u64 = 10_000_000_000_000_000_000;
+const DECIMAL_DIGITS: usize = 19;
+
+/// Signed integer of arbitrary size, stored as the sign and base `2^64` limbs of the magnitude.
+///
+/// Division and remainder truncate towards zero, as for primitive integers.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate olympic_lib;
+/// # use olympic_lib::shared::bigints::BigInt;
+/// # fn main() {
+/// let input = &mut &b"-123456789012345678901234567890 97\n"[..];
+/// readln!(input; a: BigInt, b: BigInt);
+/// assert_eq!((&a / &b).to_string(), "-1272750402189130710322005854");
+/// assert_eq!((&a % &b).to_string(), "-52");
+/// assert_eq!(&a / &b * &b + &a % &b, a);
+/// assert!(a < b && -&a > b);
+///
+/// let factorial = (1..=30).fold(BigInt::from(1), |acc, i| acc * BigInt::from(i));
+/// assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+/// assert_eq!(BigInt::from(2).pow(100).to_string(), "1267650600228229401496703205376");
+/// assert_eq!(BigInt::from(10).pow(40).isqrt(), BigInt::from(10).pow(20));
+/// assert_eq!(format!("{:>5}", BigInt::from(-42)), "  -42");
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u64>,
+}
+
+/// An error when parsing a `BigInt` from a string with characters other than an optional sign and digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("invalid digit found in string")
+    }
+}
+
+impl BigInt {
+    #[inline]
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    /// Builds a number from its sign and little-endian limbs, which may have leading zeros.
+    #[inline]
+    pub fn from_limbs(negative: bool, mut limbs: Vec<u64>) -> BigInt {
+        trim(&mut limbs);
+        BigInt{negative: negative && !limbs.is_empty(), limbs}
+    }
+
+    /// Little-endian limbs of the magnitude, without leading zeros.
+    #[inline]
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    #[inline]
+    pub fn signum(&self) -> i32 {
+        if self.negative { -1 } else if self.is_zero() { 0 } else { 1 }
+    }
+
+    #[inline]
+    pub fn abs(&self) -> BigInt {
+        BigInt{negative: false, limbs: self.limbs.clone()}
+    }
+
+    /// Number of bits in the magnitude.
+    #[inline]
+    pub fn bits(&self) -> u64 {
+        self.limbs.last().map_or(0, |&top| 64 * self.limbs.len() as u64 - top.leading_zeros() as u64)
+    }
+
+    /// Quotient and remainder at once, see `Div` and `Rem`.
+    pub fn div_rem(&self, other: &BigInt) -> (BigInt, BigInt) {
+        let (quotient, remainder) = div_rem_limbs(&self.limbs, &other.limbs);
+        (BigInt::from_limbs(self.negative != other.negative, quotient),
+         BigInt::from_limbs(self.negative, remainder))
+    }
+
+    pub fn pow(&self, mut exp: u32) -> BigInt {
+        let mut base = self.clone();
+        let mut ans = BigInt::from(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                ans = &ans * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        ans
+    }
+
+    /// Largest integer whose square is not greater than `self`, by Newton's method.
+    pub fn isqrt(&self) -> BigInt {
+        assert!(!self.negative, "Square root of a negative number");
+        if self.is_zero() {
+            return BigInt::zero();
+        }
+        let mut x = BigInt::from(1) << ((self.bits() + 1) / 2);
+        loop {
+            let y = (&x + self / &x) >> 1;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    /// The value if it fits into `i64`.
+    pub fn to_i64(&self) -> Option<i64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 if self.negative && self.limbs[0] <= 1 << 63 => Some((self.limbs[0] as i64).wrapping_neg()),
+            1 if !self.negative && self.limbs[0] < 1 << 63 => Some(self.limbs[0] as i64),
+            _ => None,
+        }
+    }
+
+    /// Nearest `f64`, up to rounding of the lower limbs.
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self.limbs.iter().rev().fold(0.0, |acc, &limb| acc * 18446744073709551616.0 + limb as f64);
+        if self.negative { -magnitude } else { magnitude }
+    }
+}
+
+#[inline]
+fn trim(limbs: &mut Vec<u64>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn cmp_limbs(a: &[u64], b: &[u64]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut ans = Vec::with_capacity(a.len() + 1);
+    let mut carry = false;
+    for (i, &x) in a.iter().enumerate() {
+        let (sum, c1) = x.overflowing_add(if i < b.len() { b[i] } else { 0 });
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        ans.push(sum);
+        carry = c1 || c2;
+    }
+    if carry {
+        ans.push(1);
+    }
+    ans
+}
+
+/// `a - b` for `a >= b`.
+fn sub_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut ans = Vec::with_capacity(a.len());
+    let mut borrow = false;
+    for (i, &x) in a.iter().enumerate() {
+        let (diff, b1) = x.overflowing_sub(if i < b.len() { b[i] } else { 0 });
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        ans.push(diff);
+        borrow = b1 || b2;
+    }
+    debug_assert!(!borrow, "Subtrahend is greater");
+    trim(&mut ans);
+    ans
+}
+
+/// Below this many limbs in the shorter factor multiplication is schoolbook.
+const KARATSUBA_THRESHOLD: usize = 32;
+/// From this many limbs in the shorter factor multiplication goes through the NTT.
+const NTT_THRESHOLD: usize = 1024;
+/// Largest total number of limbs the three-prime NTT multiplies exactly.
+const NTT_LIMIT: usize = 1 << 22;
+/// From this many limbs in the divisor and in the quotient division goes through Newton's iteration.
+const NEWTON_THRESHOLD: usize = 2048;
+
+/// Product of magnitudes, by schoolbook, Karatsuba or NTT multiplication depending on the sizes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::bigints::{div_rem_knuth, div_rem_newton, mul_karatsuba, mul_limbs, mul_ntt, mul_schoolbook};
+/// let a: Vec<u64> = (1..=3000_u64).map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15)).collect();
+/// let b: Vec<u64> = (1..=2500_u64).map(|i| i.wrapping_mul(0xBF58_476D_1CE4_E5B9)).collect();
+/// let product = mul_limbs(&a, &b);
+/// assert_eq!(mul_schoolbook(&a, &b), product);
+/// assert_eq!(mul_karatsuba(&a, &b), product);
+/// assert_eq!(mul_ntt(&a, &b), product);
+/// assert_eq!(div_rem_newton(&product, &a), (b.clone(), vec![]));
+/// let shifted = mul_limbs(&product, &[1 << 40]);
+/// assert_eq!(div_rem_newton(&shifted, &b[..2100]), div_rem_knuth(&shifted, &b[..2100]));
+/// ```
+pub fn mul_limbs(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let shorter = a.len().min(b.len());
+    if shorter < KARATSUBA_THRESHOLD {
+        mul_schoolbook(a, b)
+    } else if shorter < NTT_THRESHOLD || a.len() + b.len() > NTT_LIMIT {
+        mul_karatsuba(a, b)
+    } else {
+        mul_ntt(a, b)
+    }
+}
+
+/// Schoolbook multiplication with `big_prod` as the limb kernel, in `O(nm)`.
+pub fn mul_schoolbook(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut ans = vec![0_u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let (high, low) = big_prod(x, y);
+            let (sum, c1) = ans[i + j].overflowing_add(low);
+            let (sum, c2) = sum.overflowing_add(carry);
+            ans[i + j] = sum;
+            carry = high + c1 as u64 + c2 as u64;
+        }
+        ans[i + b.len()] = carry;
+    }
+    trim(&mut ans);
+    ans
+}
+
+/// Karatsuba multiplication in `O(n^1.59)`, the longer factor is cut into pieces if the sizes differ a lot.
+pub fn mul_karatsuba(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.len() < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+    let mut ans = Vec::new();
+    if 2 * b.len() <= a.len() {
+        for (i, chunk) in a.chunks(b.len()).enumerate() {
+            add_shifted(&mut ans, &mul_limbs(chunk, b), i * b.len());
+        }
+    } else {
+        let half = a.len() / 2;
+        let (a_low, a_high) = a.split_at(half);
+        let (b_low, b_high) = b.split_at(half);
+        let low = mul_limbs(a_low, b_low);
+        let high = mul_limbs(a_high, b_high);
+        let middle = mul_limbs(&add_limbs(a_low, a_high), &add_limbs(b_low, b_high));
+        let middle = sub_limbs(&sub_limbs(&middle, &low), &high);
+        add_shifted(&mut ans, &low, 0);
+        add_shifted(&mut ans, &middle, half);
+        add_shifted(&mut ans, &high, 2 * half);
+    }
+    trim(&mut ans);
+    ans
+}
+
+/// Multiplication by the exact three-prime NTT convolution of 32-bit pieces, in `O(n log n)`.
+///
+/// The total length is limited by `NTT_LIMIT` limbs.
+pub fn mul_ntt(a: &[u64], b: &[u64]) -> Vec<u64> {
+    assert!(a.len() + b.len() <= NTT_LIMIT, "Too long for the NTT");
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let split = |x: &[u64]| (0..2 * x.len()).map(|i| (x[i / 2] >> (32 * (i % 2))) & LOW_BITS).collect::<Vec<u64>>();
+    let product = convolution_exact(&split(a), &split(b));
+    let mut ans = Vec::with_capacity(a.len() + b.len());
+    let mut carry: u128 = 0;
+    for pair in product.chunks(2) {
+        carry += pair[0];
+        let low = carry as u64 & LOW_BITS;
+        carry >>= 32;
+        carry += pair.get(1).cloned().unwrap_or(0);
+        ans.push(low | (carry as u64) << 32);
+        carry >>= 32;
+    }
+    while carry != 0 {
+        ans.push(carry as u64);
+        carry >>= 64;
+    }
+    trim(&mut ans);
+    ans
+}
+
+/// `acc += x << (64 * offset)` in place.
+fn add_shifted(acc: &mut Vec<u64>, x: &[u64], offset: usize) {
+    if acc.len() < offset + x.len() + 1 {
+        acc.resize(offset + x.len() + 1, 0);
+    }
+    let mut carry = false;
+    let mut i = offset;
+    for &limb in x {
+        let (sum, c1) = acc[i].overflowing_add(limb);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        acc[i] = sum;
+        carry = c1 || c2;
+        i += 1;
+    }
+    while carry {
+        if i == acc.len() {
+            acc.push(0);
+        }
+        let (sum, c) = acc[i].overflowing_add(1);
+        acc[i] = sum;
+        carry = c;
+        i += 1;
+    }
+}
+
+/// `a * factor + addend` in place.
+fn mul_add_small(a: &mut Vec<u64>, factor: u64, addend: u64) {
+    let mut carry = addend;
+    for limb in a.iter_mut() {
+        let (high, low) = big_prod(*limb, factor);
+        let (sum, c) = low.overflowing_add(carry);
+        *limb = sum;
+        carry = high + c as u64;
+    }
+    if carry != 0 {
+        a.push(carry);
+    }
+}
+
+/// `a / divisor` in place, returning the remainder.
+fn div_rem_small(a: &mut Vec<u64>, divisor: u64) -> u64 {
+    let mut remainder: u64 = 0;
+    for limb in a.iter_mut().rev() {
+        let current = (remainder as u128) << 64 | *limb as u128;
+        *limb = (current / divisor as u128) as u64;
+        remainder = (current % divisor as u128) as u64;
+    }
+    trim(a);
+    remainder
+}
+
+fn shl_limbs(a: &[u64], shift: u64) -> Vec<u64> {
+    if a.is_empty() {
+        return Vec::new();
+    }
+    let (whole, bits) = ((shift / 64) as usize, (shift % 64) as u32);
+    let mut ans = vec![0; whole];
+    if bits == 0 {
+        ans.extend_from_slice(a);
+    } else {
+        let mut carry = 0;
+        for &limb in a {
+            ans.push(limb << bits | carry);
+            carry = limb >> (64 - bits);
+        }
+        ans.push(carry);
+    }
+    trim(&mut ans);
+    ans
+}
+
+fn shr_limbs(a: &[u64], shift: u64) -> Vec<u64> {
+    let (whole, bits) = ((shift / 64) as usize, (shift % 64) as u32);
+    if whole >= a.len() {
+        return Vec::new();
+    }
+    let a = &a[whole..];
+    let mut ans: Vec<u64> = if bits == 0 {
+        a.to_vec()
+    } else {
+        (0..a.len()).map(|i| a[i] >> bits | a.get(i + 1).map_or(0, |&next| next << (64 - bits))).collect()
+    };
+    trim(&mut ans);
+    ans
+}
+
+/// Quotient and remainder of magnitudes, by long division or Newton's iteration depending on the sizes.
+///
+/// Panics on division by zero.
+pub fn div_rem_limbs(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    assert!(!b.is_empty(), "Division by zero");
+    if b.len() >= NEWTON_THRESHOLD && a.len() >= b.len() + NEWTON_THRESHOLD {
+        div_rem_newton(a, b)
+    } else {
+        div_rem_knuth(a, b)
+    }
+}
+
+/// Division by the reciprocal from Newton's iteration, in a constant number of multiplications.
+pub fn div_rem_newton(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    assert!(!b.is_empty(), "Division by zero");
+    if cmp_limbs(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    let (a, b) = (BigInt::from_limbs(false, a.to_vec()), BigInt::from_limbs(false, b.to_vec()));
+    let k = a.bits();
+    // Lower bits of the dividend change the quotient by less than a unit
+    let cut = k.saturating_sub(k - b.bits() + 64);
+    let mut quotient = ((a.clone() >> cut) * reciprocal(&b, k)) >> (k - cut);
+    let mut remainder = &a - &quotient * &b;
+    // The reciprocal is off by a few units, and so is the quotient
+    while remainder.is_negative() {
+        quotient -= BigInt::from(1);
+        remainder += &b;
+    }
+    while remainder >= b {
+        quotient += BigInt::from(1);
+        remainder -= &b;
+    }
+    (quotient.limbs, remainder.limbs)
+}
+
+/// Approximately `2^k / b` for `k >= bits(b)`, off by a few units.
+///
+/// Only the top bits of `b` matter, and every Newton step `x + x (2^k - bx) / 2^k` doubles the precision.
+fn reciprocal(b: &BigInt, k: u64) -> BigInt {
+    let bits = b.bits();
+    let precision = k - bits;
+    if bits > precision + 64 {
+        let cut = bits - precision - 64;
+        return reciprocal(&(b.clone() >> cut), k - cut);
+    }
+    if precision <= 64 * NEWTON_THRESHOLD as u64 {
+        return BigInt::from_limbs(false, div_rem_knuth(&shl_limbs(&[1], k), &b.limbs).0);
+    }
+    // With x = y 2^shift the step is y 2^shift + y (2^(k - shift) - by) / 2^(k - 2 shift),
+    // and the residual is needed only to the precision of the correction
+    let half = precision / 2 + 32;
+    let shift = precision - half;
+    let y = reciprocal(b, bits + half);
+    let error = (BigInt::from(1) << (k - shift)) - b * &y;
+    let cut = error.bits().saturating_sub(precision - half + 64).min(k - 2 * shift);
+    (y.clone() << shift) + ((&y * (error >> cut)) >> (k - 2 * shift - cut))
+}
+
+/// Long division (Knuth's algorithm D) of magnitudes in `O(nm)`, panics on division by zero.
+pub fn div_rem_knuth(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    assert!(!b.is_empty(), "Division by zero");
+    if cmp_limbs(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let mut quotient = a.to_vec();
+        let remainder = div_rem_small(&mut quotient, b[0]);
+        return (quotient, if remainder == 0 { Vec::new() } else { vec![remainder] });
+    }
+    // Normalize so that the top limb of the divisor has its high bit set
+    let shift = b[b.len() - 1].leading_zeros() as u64;
+    let b = shl_limbs(b, shift);
+    let mut a = shl_limbs(a, shift);
+    a.resize(a.len().max(b.len()) + 1, 0);
+    if a[a.len() - 1] != 0 {
+        a.push(0);
+    }
+    let n = b.len();
+    let m = a.len() - n;
+    let (top, second) = (b[n - 1] as u128, b[n - 2] as u128);
+    let mut quotient = vec![0; m];
+    for j in (0..m).rev() {
+        let numerator = (a[j + n] as u128) << 64 | a[j + n - 1] as u128;
+        let mut qhat = numerator / top;
+        let mut rhat = numerator % top;
+        while qhat >> 64 != 0 || qhat * second > (rhat << 64 | a[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += top;
+            if rhat >> 64 != 0 {
+                break;
+            }
+        }
+        let mut carry: u128 = 0;
+        let mut borrow = false;
+        for i in 0..n {
+            let product = qhat * b[i] as u128 + carry;
+            carry = product >> 64;
+            let (diff, b1) = a[i + j].overflowing_sub(product as u64);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            a[i + j] = diff;
+            borrow = b1 || b2;
+        }
+        let (diff, b1) = a[j + n].overflowing_sub(carry as u64);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        a[j + n] = diff;
+        if b1 || b2 {
+            qhat -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                let (sum, c1) = a[i + j].overflowing_add(b[i]);
+                let (sum, c2) = sum.overflowing_add(carry as u64);
+                a[i + j] = sum;
+                carry = c1 || c2;
+            }
+            a[j + n] = a[j + n].wrapping_add(carry as u64);
+        }
+        quotient[j] = qhat as u64;
+    }
+    trim(&mut quotient);
+    (quotient, shr_limbs(&a[..n], shift))
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_limbs(&self.limbs, &other.limbs),
+            (true, true) => cmp_limbs(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    #[inline]
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_from_primitive {
+    ($($t:ty),*) => ($(
+        impl From<$t> for BigInt {
+            #[inline]
+            #[allow(unused_comparisons)]
+            fn from(value: $t) -> BigInt {
+                let magnitude = if value < 0 { (value as i128).unsigned_abs() } else { value as u128 };
+                BigInt::from_limbs(value < 0, vec![magnitude as u64, (magnitude >> 64) as u64])
+            }
+        }
+    )*);
+}
+
+impl_from_primitive!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+    fn from_str(s: &str) -> Result<BigInt, ParseBigIntError> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(&b'-') => (true, &s[1..]),
+            Some(&b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(ParseBigIntError);
+        }
+        let mut limbs = Vec::with_capacity(digits.len() / DECIMAL_DIGITS + 1);
+        let head = digits.len() % DECIMAL_DIGITS;
+        let mut start = 0;
+        let mut end = if head == 0 { DECIMAL_DIGITS } else { head };
+        while start < digits.len() {
+            let chunk: u64 = digits[start..end].parse().map_err(|_| ParseBigIntError)?;
+            mul_add_small(&mut limbs, 10_u64.pow((end - start) as u32), chunk);
+            start = end;
+            end += DECIMAL_DIGITS;
+        }
+        Ok(BigInt::from_limbs(negative, limbs))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let mut limbs = self.limbs.clone();
+        let mut chunks = Vec::with_capacity(limbs.len() * 20 / DECIMAL_DIGITS + 1);
+        while !limbs.is_empty() {
+            chunks.push(div_rem_small(&mut limbs, DECIMAL_BASE));
+        }
+        let mut digits = chunks.last().map_or("0".to_string(), |top| top.to_string());
+        for chunk in chunks.iter().rev().skip(1) {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+    #[inline]
+    fn neg(self) -> BigInt {
+        BigInt{negative: !self.negative && !self.is_zero(), limbs: self.limbs}
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+    #[inline]
+    fn neg(self) -> BigInt {
+        -self.clone()
+    }
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_limbs(self.negative, add_limbs(&self.limbs, &other.limbs));
+        }
+        match cmp_limbs(&self.limbs, &other.limbs) {
+            Ordering::Less => BigInt::from_limbs(other.negative, sub_limbs(&other.limbs, &self.limbs)),
+            _ => BigInt::from_limbs(self.negative, sub_limbs(&self.limbs, &other.limbs)),
+        }
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+    #[inline]
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+    #[inline]
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_limbs(self.negative != other.negative, mul_limbs(&self.limbs, &other.limbs))
+    }
+}
+
+impl Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+    #[inline]
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+    #[inline]
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+macro_rules! forward_binop {
+    ($($imp:ident, $method:ident, $assign_imp:ident, $assign_method:ident);*) => ($(
+        impl $imp<BigInt> for BigInt {
+            type Output = BigInt;
+            #[inline]
+            fn $method(self, other: BigInt) -> BigInt {
+                (&self).$method(&other)
+            }
+        }
+        impl<'a> $imp<&'a BigInt> for BigInt {
+            type Output = BigInt;
+            #[inline]
+            fn $method(self, other: &BigInt) -> BigInt {
+                (&self).$method(other)
+            }
+        }
+        impl<'a> $imp<BigInt> for &'a BigInt {
+            type Output = BigInt;
+            #[inline]
+            fn $method(self, other: BigInt) -> BigInt {
+                self.$method(&other)
+            }
+        }
+        impl $assign_imp<BigInt> for BigInt {
+            #[inline]
+            fn $assign_method(&mut self, other: BigInt) {
+                *self = (&*self).$method(&other);
+            }
+        }
+        impl<'a> $assign_imp<&'a BigInt> for BigInt {
+            #[inline]
+            fn $assign_method(&mut self, other: &BigInt) {
+                *self = (&*self).$method(other);
+            }
+        }
+    )*);
+}
+
+forward_binop!(Add, add, AddAssign, add_assign; Sub, sub, SubAssign, sub_assign;
+               Mul, mul, MulAssign, mul_assign; Div, div, DivAssign, div_assign;
+               Rem, rem, RemAssign, rem_assign);
+
+impl ::std::ops::Shl<u64> for BigInt {
+    type Output = BigInt;
+    #[inline]
+    fn shl(self, shift: u64) -> BigInt {
+        BigInt::from_limbs(self.negative, shl_limbs(&self.limbs, shift))
+    }
+}
+
+/// Shifts the magnitude, so negative numbers are rounded towards zero.
+impl ::std::ops::Shr<u64> for BigInt {
+    type Output = BigInt;
+    #[inline]
+    fn shr(self, shift: u64) -> BigInt {
+        BigInt::from_limbs(self.negative, shr_limbs(&self.limbs, shift))
+    }
+}
@@ -0,0 +1,305 @@
+use std::iter::Map;
+use std::ops::{Add, Div, Mul, Range, Sub};
+
+/// Iterator over all submasks of a mask in decreasing order, from the mask itself down to `0`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::bitmask::{Bits, Submasks};
+/// assert_eq!(Submasks::new(0b101).collect::<Vec<_>>(), vec![0b101, 0b100, 0b001, 0]);
+/// assert_eq!(Submasks::new(0).collect::<Vec<_>>(), vec![0]);
+/// assert_eq!(Bits::new(0b10110).collect::<Vec<_>>(), vec![1, 2, 4]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Submasks {
+    mask: usize,
+    next: Option<usize>,
+}
+
+impl Submasks {
+    #[inline]
+    pub fn new(mask: usize) -> Submasks {
+        Submasks{mask, next: Some(mask)}
+    }
+}
+
+impl Iterator for Submasks {
+    type Item = usize;
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let current = self.next?;
+        self.next = if current == 0 { None } else { Some((current - 1) & self.mask) };
+        Some(current)
+    }
+}
+
+/// Mask of the lowest `n` bits, all of them for `n >= usize::BITS`.
+#[inline]
+fn low_bits(n: u32) -> usize {
+    if n >= usize::BITS { usize::MAX } else { (1 << n) - 1 }
+}
+
+/// Iterator over all supersets of a mask among `n`-bit masks, in increasing order.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::bitmask::Supersets;
+/// assert_eq!(Supersets::new(0b010, 3).collect::<Vec<_>>(), vec![0b010, 0b011, 0b110, 0b111]);
+/// assert_eq!(Supersets::new(usize::MAX - 1, usize::BITS).collect::<Vec<_>>(), vec![usize::MAX - 1, usize::MAX]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Supersets {
+    full: usize,
+    mask: usize,
+    next: Option<usize>,
+}
+
+impl Supersets {
+    #[inline]
+    pub fn new(mask: usize, n: u32) -> Supersets {
+        let full = low_bits(n);
+        Supersets{full, mask, next: if mask & !full == 0 { Some(mask) } else { None }}
+    }
+}
+
+impl Iterator for Supersets {
+    type Item = usize;
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let current = self.next?;
+        self.next = if current == self.full { None } else { Some((current + 1) | self.mask) };
+        Some(current)
+    }
+}
+
+/// Iterator over all `n`-bit masks with exactly `k` bits set, in increasing order (Gosper's hack).
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::bitmask::Combinations;
+/// assert_eq!(Combinations::new(4, 2).collect::<Vec<_>>(), vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]);
+/// assert_eq!(Combinations::new(3, 0).collect::<Vec<_>>(), vec![0]);
+/// assert_eq!(Combinations::new(2, 3).count(), 0);
+/// assert_eq!(Combinations::new(usize::BITS, usize::BITS).collect::<Vec<_>>(), vec![usize::MAX]);
+/// assert_eq!(Combinations::new(usize::BITS, 1).last(), Some(1 << (usize::BITS - 1)));
+/// assert_eq!(Combinations::new(usize::BITS, usize::BITS - 1).count(), usize::BITS as usize);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Combinations {
+    full: usize,
+    next: Option<usize>,
+}
+
+impl Combinations {
+    #[inline]
+    pub fn new(n: u32, k: u32) -> Combinations {
+        Combinations{full: low_bits(n), next: if k <= n { Some(low_bits(k)) } else { None }}
+    }
+}
+
+impl Iterator for Combinations {
+    type Item = usize;
+    fn next(&mut self) -> Option<usize> {
+        let current = self.next?;
+        self.next = if current == 0 {
+            None
+        } else {
+            let lowest = current & current.wrapping_neg();
+            // Overflow means the highest block of ones already ends at the top bit
+            current.checked_add(lowest).map(|ripple| (((ripple ^ current) >> 2) / lowest) | ripple)
+                .filter(|&next| next & !self.full == 0)
+        };
+        Some(current)
+    }
+}
+
+/// Iterator over the indices of the set bits of a mask, in increasing order.
+#[derive(Debug, Clone)]
+pub struct Bits {
+    mask: usize,
+}
+
+impl Bits {
+    #[inline]
+    pub fn new(mask: usize) -> Bits {
+        Bits{mask}
+    }
+}
+
+impl Iterator for Bits {
+    type Item = usize;
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        if self.mask == 0 {
+            return None;
+        }
+        let bit = self.mask.trailing_zeros() as usize;
+        self.mask &= self.mask - 1;
+        Some(bit)
+    }
+}
+
+/// All `n`-bit masks in Gray code order, where neighbours differ in one bit.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::bitmask::gray_code;
+/// assert_eq!(gray_code(3).collect::<Vec<_>>(), vec![0, 1, 3, 2, 6, 7, 5, 4]);
+/// ```
+#[inline]
+pub fn gray_code(n: u32) -> Map<Range<usize>, fn(usize) -> usize> {
+    (0..1 << n).map(|i| i ^ (i >> 1))
+}
+
+fn check_len<T>(a: &[T]) {
+    assert!(a.len().is_power_of_two(), "Length is not a power of two");
+}
+
+/// Replaces `a[mask]` by the sum of `a[sub]` over all submasks `sub`, in `O(n 2^n)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::bitmask::{subset_mobius, subset_zeta, superset_mobius, superset_zeta};
+/// let mut a = vec![1, 2, 3, 4];
+/// subset_zeta(&mut a);
+/// assert_eq!(a, vec![1, 3, 4, 10]);
+/// subset_mobius(&mut a);
+/// assert_eq!(a, vec![1, 2, 3, 4]);
+/// superset_zeta(&mut a);
+/// assert_eq!(a, vec![10, 6, 7, 4]);
+/// superset_mobius(&mut a);
+/// assert_eq!(a, vec![1, 2, 3, 4]);
+/// ```
+pub fn subset_zeta<T: Copy + Add<Output=T>>(a: &mut [T]) {
+    check_len(a);
+    let mut bit = 1;
+    while bit < a.len() {
+        for mask in 0..a.len() {
+            if mask & bit != 0 {
+                a[mask] = a[mask] + a[mask ^ bit];
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// Inverse of `subset_zeta`.
+pub fn subset_mobius<T: Copy + Sub<Output=T>>(a: &mut [T]) {
+    check_len(a);
+    let mut bit = 1;
+    while bit < a.len() {
+        for mask in 0..a.len() {
+            if mask & bit != 0 {
+                a[mask] = a[mask] - a[mask ^ bit];
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// Replaces `a[mask]` by the sum of `a[sup]` over all supersets `sup`, in `O(n 2^n)`.
+pub fn superset_zeta<T: Copy + Add<Output=T>>(a: &mut [T]) {
+    check_len(a);
+    let mut bit = 1;
+    while bit < a.len() {
+        for mask in 0..a.len() {
+            if mask & bit == 0 {
+                a[mask] = a[mask] + a[mask | bit];
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// Inverse of `superset_zeta`.
+pub fn superset_mobius<T: Copy + Sub<Output=T>>(a: &mut [T]) {
+    check_len(a);
+    let mut bit = 1;
+    while bit < a.len() {
+        for mask in 0..a.len() {
+            if mask & bit == 0 {
+                a[mask] = a[mask] - a[mask | bit];
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// Unnormalized Walsh–Hadamard transform: applying it twice multiplies `a` by its length.
+pub fn walsh_hadamard<T: Copy + Add<Output=T> + Sub<Output=T>>(a: &mut [T]) {
+    check_len(a);
+    let mut half = 1;
+    while half < a.len() {
+        for start in (0..a.len()).step_by(2 * half) {
+            for i in start..start + half {
+                let (u, v) = (a[i], a[i + half]);
+                a[i] = u + v;
+                a[i + half] = u - v;
+            }
+        }
+        half <<= 1;
+    }
+}
+
+/// `ans[k]` is the sum of `a[i] * b[j]` over `i | j == k`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::bitmask::{and_convolution, or_convolution, xor_convolution};
+/// let (a, b) = (vec![1_i64, 2, 3, 4], vec![5, 6, 7, 8]);
+/// let naive = |op: fn(usize, usize) -> usize| {
+///     let mut ans = vec![0; 4];
+///     for i in 0..4 {
+///         for j in 0..4 {
+///             ans[op(i, j)] += a[i] * b[j];
+///         }
+///     }
+///     ans
+/// };
+/// assert_eq!(or_convolution(&a, &b), naive(|i, j| i | j));
+/// assert_eq!(and_convolution(&a, &b), naive(|i, j| i & j));
+/// assert_eq!(xor_convolution(&a, &b), naive(|i, j| i ^ j));
+/// ```
+pub fn or_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+    where T: Copy + Add<Output=T> + Sub<Output=T> + Mul<Output=T> {
+    assert_eq!(a.len(), b.len(), "Lengths differ");
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    subset_zeta(&mut a);
+    subset_zeta(&mut b);
+    let mut ans: Vec<T> = a.iter().zip(&b).map(|(&x, &y)| x * y).collect();
+    subset_mobius(&mut ans);
+    ans
+}
+
+/// `ans[k]` is the sum of `a[i] * b[j]` over `i & j == k`.
+pub fn and_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+    where T: Copy + Add<Output=T> + Sub<Output=T> + Mul<Output=T> {
+    assert_eq!(a.len(), b.len(), "Lengths differ");
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    superset_zeta(&mut a);
+    superset_zeta(&mut b);
+    let mut ans: Vec<T> = a.iter().zip(&b).map(|(&x, &y)| x * y).collect();
+    superset_mobius(&mut ans);
+    ans
+}
+
+/// `ans[k]` is the sum of `a[i] * b[j]` over `i ^ j == k`.
+///
+/// The length must be invertible in `T`, which holds for integers, floats and odd moduli.
+pub fn xor_convolution<T>(a: &[T], b: &[T]) -> Vec<T>
+    where T: Copy + Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Div<Output=T> + From<u32> {
+    assert_eq!(a.len(), b.len(), "Lengths differ");
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    walsh_hadamard(&mut a);
+    walsh_hadamard(&mut b);
+    let mut ans: Vec<T> = a.iter().zip(&b).map(|(&x, &y)| x * y).collect();
+    walsh_hadamard(&mut ans);
+    let len = T::from(ans.len() as u32);
+    ans.iter().map(|&x| x / len).collect()
+}
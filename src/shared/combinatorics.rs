@@ -0,0 +1,256 @@
+use std::ops::{Add, Mul, Sub};
+use shared::modint::{ModInt, Modulus};
+
+/// Factorials and inverse factorials up to `n` modulo a prime greater than `n`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::combinatorics::Factorials;
+/// # use olympic_lib::shared::modint::{Const, ModInt998244353};
+/// let f: Factorials<Const<998_244_353>> = Factorials::new(100);
+/// assert_eq!((f.binomial(5, 2).value(), f.binomial(5, 6).value(), f.binomial(0, 0).value()), (10, 0, 1));
+/// assert_eq!(f.permutations(5, 2).value(), 20);
+/// assert_eq!(f.multinomial(&[1, 2, 3]).value(), 60);
+/// assert_eq!(f.stars_and_bars(3, 2).value(), 4);
+/// assert_eq!(f.catalan(10).value(), 16796);
+/// assert_eq!(f.fact(20) * f.inv_fact(20), ModInt998244353::from(1));
+///
+/// // Lucas's theorem modulo 7 needs the table only up to 6
+/// let small: Factorials<Const<7>> = Factorials::new(6);
+/// assert_eq!(small.lucas(558_545_864_083_284_006, 123_456_789).value(), 6);
+/// assert_eq!(small.lucas(1000, 301).value(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Factorials<M> {
+    fact: Vec<ModInt<M>>,
+    inv_fact: Vec<ModInt<M>>,
+}
+
+impl<M: Modulus> Factorials<M> {
+    pub fn new(n: usize) -> Factorials<M> {
+        let mut fact = Vec::with_capacity(n + 1);
+        fact.push(ModInt::from(1_u32));
+        for i in 1..=n {
+            fact.push(fact[i - 1] * ModInt::from(i));
+        }
+        let mut inv_fact = vec![ModInt::default(); n + 1];
+        inv_fact[n] = fact[n].inv();
+        for i in (1..=n).rev() {
+            inv_fact[i - 1] = inv_fact[i] * ModInt::from(i);
+        }
+        Factorials{fact, inv_fact}
+    }
+
+    #[inline]
+    pub fn fact(&self, n: usize) -> ModInt<M> {
+        self.fact[n]
+    }
+
+    #[inline]
+    pub fn inv_fact(&self, n: usize) -> ModInt<M> {
+        self.inv_fact[n]
+    }
+
+    /// Ways to choose `k` of `n` items, zero if `k > n`.
+    #[inline]
+    pub fn binomial(&self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::default();
+        }
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+
+    /// Ways to arrange `k` of `n` items in a row, zero if `k > n`.
+    #[inline]
+    pub fn permutations(&self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::default();
+        }
+        self.fact[n] * self.inv_fact[n - k]
+    }
+
+    /// Ways to split `parts.iter().sum()` items into labelled groups of the given sizes.
+    pub fn multinomial(&self, parts: &[usize]) -> ModInt<M> {
+        parts.iter().fold(self.fact[parts.iter().sum::<usize>()], |acc, &part| acc * self.inv_fact[part])
+    }
+
+    /// Ways to put `n` identical items into `k` boxes.
+    pub fn stars_and_bars(&self, n: usize, k: usize) -> ModInt<M> {
+        if k == 0 {
+            return ModInt::from((n == 0) as u32);
+        }
+        self.binomial(n + k - 1, k - 1)
+    }
+
+    /// `n`-th Catalan number `C(2n, n) / (n + 1)`, the table must go up to `2n`.
+    #[inline]
+    pub fn catalan(&self, n: usize) -> ModInt<M> {
+        assert!(2 * n < self.fact.len(), "The table is shorter than 2n");
+        self.fact[2 * n] * self.inv_fact[n] * self.inv_fact[n + 1]
+    }
+
+    /// `C(n, k)` for huge `n` by Lucas's theorem, the table must go up to `modulus - 1`.
+    pub fn lucas(&self, mut n: u64, mut k: u64) -> ModInt<M> {
+        let p = M::modulus() as u64;
+        assert!(self.fact.len() as u64 >= p, "The table is shorter than the modulus");
+        let mut ans = ModInt::from(1_u32);
+        while k > 0 {
+            ans *= self.binomial((n % p) as usize, (k % p) as usize);
+            n /= p;
+            k /= p;
+        }
+        ans
+    }
+}
+
+/// Exact `C(n, k)`, or `None` if it does not fit into `u64`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::combinatorics::binomial_u64;
+/// assert_eq!(binomial_u64(67, 33), Some(14226520737620288370));
+/// assert_eq!(binomial_u64(68, 34), None);
+/// assert_eq!(binomial_u64(u64::MAX, 1), Some(u64::MAX));
+/// assert_eq!(binomial_u64(3, 5), Some(0));
+/// ```
+pub fn binomial_u64(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut ans: u128 = 1;
+    for i in 0..k {
+        // ans is C(n, i), which fits into u64, so the product stays below 2^128
+        ans = ans * (n - i) as u128 / (i + 1) as u128;
+        if ans > u64::MAX as u128 {
+            return None;
+        }
+    }
+    Some(ans as u64)
+}
+
+/// Pascal's triangle: `ans[i][j]` is `C(i, j)` for `j <= i <= n`.
+///
+/// Every table here works with `u64` or `BigInt` for exact values and with `ModInt` for residues.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::bigints::BigInt;
+/// # use olympic_lib::shared::combinatorics::{bell, catalan, partitions, pascal, stirling_first, stirling_second};
+/// # use olympic_lib::shared::modint::ModInt1000000007;
+/// assert_eq!(pascal::<u64>(4)[4], vec![1, 4, 6, 4, 1]);
+/// assert_eq!(catalan::<u64>(6), vec![1, 1, 2, 5, 14, 42, 132]);
+/// assert_eq!(stirling_first::<u64>(4)[4], vec![0, 6, 11, 6, 1]);
+/// assert_eq!(stirling_second::<u64>(4)[4], vec![0, 1, 7, 6, 1]);
+/// assert_eq!(bell::<u64>(6), vec![1, 1, 2, 5, 15, 52, 203]);
+/// assert_eq!(partitions::<u64>(10), vec![1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42]);
+///
+/// assert_eq!(partitions::<BigInt>(1000)[1000].to_string(), "24061467864032622473692149727991");
+/// assert_eq!(bell::<BigInt>(30)[30].to_string(), "846749014511809332450147");
+/// assert_eq!(catalan::<ModInt1000000007>(100)[100].value(), 558488487);
+/// ```
+pub fn pascal<T>(n: usize) -> Vec<Vec<T>>
+    where T: Clone + Add<Output=T> + From<u32> {
+    let mut ans: Vec<Vec<T>> = Vec::with_capacity(n + 1);
+    for i in 0..=n {
+        let mut row = Vec::with_capacity(i + 1);
+        row.push(T::from(1));
+        for j in 1..i {
+            row.push(ans[i - 1][j - 1].clone() + ans[i - 1][j].clone());
+        }
+        if i > 0 {
+            row.push(T::from(1));
+        }
+        ans.push(row);
+    }
+    ans
+}
+
+/// Catalan numbers `C_0..=C_n` by the convolution recurrence, in `O(n^2)`.
+pub fn catalan<T>(n: usize) -> Vec<T>
+    where T: Clone + Add<Output=T> + Mul<Output=T> + From<u32> {
+    let mut ans: Vec<T> = Vec::with_capacity(n + 1);
+    ans.push(T::from(1));
+    for i in 1..=n {
+        let next = (0..i).fold(T::from(0), |acc, j| acc + ans[j].clone() * ans[i - 1 - j].clone());
+        ans.push(next);
+    }
+    ans
+}
+
+/// Unsigned Stirling numbers of the first kind: `ans[i][j]` permutations of `i` items with `j` cycles.
+pub fn stirling_first<T>(n: usize) -> Vec<Vec<T>>
+    where T: Clone + Add<Output=T> + Mul<Output=T> + From<u32> {
+    stirling(n, |i, _| i)
+}
+
+/// Stirling numbers of the second kind: `ans[i][j]` partitions of `i` items into `j` non-empty sets.
+pub fn stirling_second<T>(n: usize) -> Vec<Vec<T>>
+    where T: Clone + Add<Output=T> + Mul<Output=T> + From<u32> {
+    stirling(n, |_, j| j)
+}
+
+/// `ans[i + 1][j] = factor(i, j) * ans[i][j] + ans[i][j - 1]`.
+fn stirling<T, F>(n: usize, factor: F) -> Vec<Vec<T>>
+    where T: Clone + Add<Output=T> + Mul<Output=T> + From<u32>, F: Fn(usize, usize) -> usize {
+    let mut ans: Vec<Vec<T>> = Vec::with_capacity(n + 1);
+    ans.push(vec![T::from(1)]);
+    for i in 0..n {
+        let mut row = Vec::with_capacity(i + 2);
+        row.push(T::from(0));
+        for j in 1..=i + 1 {
+            let kept = if j <= i { T::from(factor(i, j) as u32) * ans[i][j].clone() } else { T::from(0) };
+            row.push(kept + ans[i][j - 1].clone());
+        }
+        ans.push(row);
+    }
+    ans
+}
+
+/// Bell numbers `B_0..=B_n`, the numbers of partitions of a set, by the Bell triangle in `O(n^2)`.
+pub fn bell<T>(n: usize) -> Vec<T>
+    where T: Clone + Add<Output=T> + From<u32> {
+    let mut ans = Vec::with_capacity(n + 1);
+    ans.push(T::from(1));
+    let mut row = vec![T::from(1)];
+    for _ in 0..n {
+        ans.push(row[row.len() - 1].clone());
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(row[row.len() - 1].clone());
+        for value in row {
+            let sum = next[next.len() - 1].clone() + value;
+            next.push(sum);
+        }
+        row = next;
+    }
+    ans
+}
+
+/// Partition numbers `p(0)..=p(n)` by Euler's pentagonal number theorem, in `O(n sqrt n)`.
+pub fn partitions<T>(n: usize) -> Vec<T>
+    where T: Clone + Add<Output=T> + Sub<Output=T> + From<u32> {
+    let mut ans = Vec::with_capacity(n + 1);
+    ans.push(T::from(1));
+    for i in 1..=n {
+        // Positive and negative terms apart, so unsigned types never go below zero
+        let (mut plus, mut minus) = (T::from(0), T::from(0));
+        let mut k = 1;
+        while k * (3 * k - 1) / 2 <= i {
+            for pentagonal in [k * (3 * k - 1) / 2, k * (3 * k + 1) / 2].iter() {
+                if *pentagonal <= i {
+                    if k % 2 == 1 {
+                        plus = plus + ans[i - pentagonal].clone();
+                    } else {
+                        minus = minus + ans[i - pentagonal].clone();
+                    }
+                }
+            }
+            k += 1;
+        }
+        ans.push(plus - minus);
+    }
+    ans
+}
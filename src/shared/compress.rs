@@ -0,0 +1,144 @@
+use std::iter::FromIterator;
+
+use shared::slices_extensions::{bisect_left_by, bisect_right_by};
+
+/// Sorted distinct values, mapped to their ranks `0..len()` and back.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::compress::Compressor;
+/// let c: Compressor<i64> = vec![100, -5, 100, 7].into_iter().collect();
+/// assert_eq!(c.values(), &[-5, 7, 100]);
+/// assert_eq!(c.compress(&[100, -5, 7]), vec![2, 0, 1]);
+/// assert_eq!((c.rank(&7), c.get_rank(&8), *c.value(2)), (1, None, 100));
+/// assert_eq!((c.lower_rank(&8), c.upper_rank(&7), c.upper_rank(&1000)), (2, 2, 3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compressor<T> {
+    values: Vec<T>,
+}
+
+impl<T: Ord> Compressor<T> {
+    pub fn new(mut values: Vec<T>) -> Compressor<T> {
+        values.sort_unstable();
+        values.dedup();
+        Compressor{values}
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    #[inline]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Value of the given rank.
+    #[inline]
+    pub fn value(&self, rank: usize) -> &T {
+        &self.values[rank]
+    }
+
+    /// Rank of `value`, if it was inserted.
+    #[inline]
+    pub fn get_rank(&self, value: &T) -> Option<usize> {
+        let rank = bisect_left_by(&self.values, |item| item.cmp(value));
+        if rank < self.values.len() && self.values[rank] == *value { Some(rank) } else { None }
+    }
+
+    /// Rank of an inserted `value`, panics otherwise.
+    #[inline]
+    pub fn rank(&self, value: &T) -> usize {
+        self.get_rank(value).expect("Value was not compressed")
+    }
+
+    /// Number of values less than `value`, which need not be inserted.
+    #[inline]
+    pub fn lower_rank(&self, value: &T) -> usize {
+        bisect_left_by(&self.values, |item| item.cmp(value))
+    }
+
+    /// Number of values not greater than `value`, which need not be inserted.
+    #[inline]
+    pub fn upper_rank(&self, value: &T) -> usize {
+        bisect_right_by(&self.values, |item| item.cmp(value))
+    }
+
+    /// Ranks of inserted values.
+    #[inline]
+    pub fn compress(&self, values: &[T]) -> Vec<usize> {
+        values.iter().map(|v| self.rank(v)).collect()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for Compressor<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Compressor<T> {
+        Compressor::new(iter.into_iter().collect())
+    }
+}
+
+/// Independent compression of both coordinates of a point set.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::compress::Compressor2d;
+/// let c = Compressor2d::new(&[(10, -1), (3, 8), (10, 5)]);
+/// assert_eq!((c.xs().len(), c.ys().len()), (2, 3));
+/// assert_eq!(c.compress(&[(10, -1), (3, 8)]), vec![(1, 0), (0, 2)]);
+/// assert_eq!(c.lower_rank(&(4, 5)), (1, 1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compressor2d<X, Y> {
+    xs: Compressor<X>,
+    ys: Compressor<Y>,
+}
+
+impl<X: Ord + Clone, Y: Ord + Clone> Compressor2d<X, Y> {
+    pub fn new(points: &[(X, Y)]) -> Compressor2d<X, Y> {
+        Compressor2d{xs: points.iter().map(|p| p.0.clone()).collect(),
+                     ys: points.iter().map(|p| p.1.clone()).collect()}
+    }
+
+    #[inline]
+    pub fn xs(&self) -> &Compressor<X> {
+        &self.xs
+    }
+
+    #[inline]
+    pub fn ys(&self) -> &Compressor<Y> {
+        &self.ys
+    }
+
+    /// Ranks of both coordinates of an inserted point.
+    #[inline]
+    pub fn rank(&self, point: &(X, Y)) -> (usize, usize) {
+        (self.xs.rank(&point.0), self.ys.rank(&point.1))
+    }
+
+    /// Lower ranks of both coordinates of any point, see `Compressor::lower_rank`.
+    #[inline]
+    pub fn lower_rank(&self, point: &(X, Y)) -> (usize, usize) {
+        (self.xs.lower_rank(&point.0), self.ys.lower_rank(&point.1))
+    }
+
+    /// Upper ranks of both coordinates of any point, see `Compressor::upper_rank`.
+    #[inline]
+    pub fn upper_rank(&self, point: &(X, Y)) -> (usize, usize) {
+        (self.xs.upper_rank(&point.0), self.ys.upper_rank(&point.1))
+    }
+
+    #[inline]
+    pub fn compress(&self, points: &[(X, Y)]) -> Vec<(usize, usize)> {
+        points.iter().map(|p| self.rank(p)).collect()
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt::Debug;
+
+use shared::geometry::Point;
+use shared::graph::RectangleGraph;
+use shared::grid::Grid;
+
+/// Representation used by `debug!` for types with a better layout than `{:?}`.
+///
+/// Tables are printed one row per line.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::debug::PrettyDebug;
+/// # use olympic_lib::shared::geometry::Point;
+/// assert_eq!(vec![vec![1, 2], vec![3, 4]].pretty_debug(), "[1, 2]\n[3, 4]");
+/// assert_eq!(Point::new(3, -4).pretty_debug(), "(3, -4)");
+/// ```
+pub trait PrettyDebug {
+    fn pretty_debug(&self) -> String;
+}
+
+/// `{:?}` for everything else, picked by `debug!` only when `PrettyDebug` is not implemented.
+pub trait FallbackDebug {
+    fn pretty_debug(&self) -> String;
+}
+
+impl<T: Debug + ?Sized> FallbackDebug for &T {
+    #[inline]
+    fn pretty_debug(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl<T: Debug> PrettyDebug for Vec<Vec<T>> {
+    fn pretty_debug(&self) -> String {
+        self.iter().map(|row| format!("{:?}", row)).collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl<T: Debug> PrettyDebug for Grid<T> {
+    fn pretty_debug(&self) -> String {
+        self.rows().map(|row| format!("{:?}", row)).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Active cells are `.`, the others are `#`, with `y` growing downwards.
+impl PrettyDebug for RectangleGraph {
+    fn pretty_debug(&self) -> String {
+        (0..self.height())
+            .map(|y| (0..self.width()).map(|x| if self.bstate(x, y) { '.' } else { '#' }).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T: Debug> PrettyDebug for Point<T> {
+    #[inline]
+    fn pretty_debug(&self) -> String {
+        format!("({:?}, {:?})", self.x, self.y)
+    }
+}
+
+/// Joins the `(name, value)` entries of `debug!` into one message.
+///
+/// Multiline values start on a new line and end the line they are on.
+pub fn join_entries(entries: &[(&str, String)]) -> String {
+    let mut ans = String::new();
+    for (i, &(name, ref value)) in entries.iter().enumerate() {
+        let multiline = value.contains('\n');
+        if i != 0 {
+            ans.push_str(if ans.ends_with('\n') { "" } else { ", " });
+        }
+        ans.push_str(name);
+        ans.push_str(if multiline { " =\n" } else { " = " });
+        ans.push_str(value);
+        if multiline && i + 1 != entries.len() {
+            ans.push('\n');
+        }
+    }
+    ans
+}
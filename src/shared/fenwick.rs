@@ -0,0 +1,89 @@
+use std::ops::{AddAssign, Range, Sub};
+
+/// Binary indexed tree: point additions and prefix sums in `O(log n)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::fenwick::Fenwick;
+/// let mut f = Fenwick::new(5);
+/// f.add(1, 3);
+/// f.add(3, 4);
+/// f.add(4, -1);
+/// assert_eq!((f.prefix_sum(2), f.prefix_sum(5), f.range_sum(2..5)), (3, 6, 3));
+/// assert_eq!(f.lower_bound(4), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fenwick<T> {
+    tree: Vec<T>,
+}
+
+impl<T> Fenwick<T>
+    where T: Copy + Default + AddAssign + Sub<Output=T> {
+    #[inline]
+    pub fn new(n: usize) -> Fenwick<T> {
+        Fenwick{tree: vec![T::default(); n]}
+    }
+
+    /// Builds the tree over `values` in `O(n)`.
+    pub fn from_slice(values: &[T]) -> Fenwick<T> {
+        let mut tree = values.to_vec();
+        for i in 0..tree.len() {
+            let parent = i | (i + 1);
+            if parent < tree.len() {
+                let value = tree[i];
+                tree[parent] += value;
+            }
+        }
+        Fenwick{tree}
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    pub fn add(&mut self, mut idx: usize, delta: T) {
+        while idx < self.tree.len() {
+            self.tree[idx] += delta;
+            idx |= idx + 1;
+        }
+    }
+
+    /// Sum of the first `end` values.
+    pub fn prefix_sum(&self, mut end: usize) -> T {
+        let mut ans = T::default();
+        while end > 0 {
+            ans += self.tree[end - 1];
+            end &= end - 1;
+        }
+        ans
+    }
+
+    #[inline]
+    pub fn range_sum(&self, range: Range<usize>) -> T {
+        self.prefix_sum(range.end) - self.prefix_sum(range.start)
+    }
+
+    /// Smallest `idx` with `prefix_sum(idx + 1) >= target`, or `len()` if there is none.
+    ///
+    /// Requires all values to be non-negative.
+    pub fn lower_bound(&self, mut target: T) -> usize
+        where T: PartialOrd {
+        let mut pos = 0;
+        let mut step = self.tree.len().next_power_of_two();
+        while step > 0 {
+            if pos + step <= self.tree.len() && self.tree[pos + step - 1] < target {
+                pos += step;
+                target = target - self.tree[pos - 1];
+            }
+            step >>= 1;
+        }
+        pos
+    }
+}
@@ -0,0 +1,224 @@
+use std::f64::consts::PI;
+use std::ops::{Add, Mul, Sub};
+
+/// Complex number of two `f64`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    #[inline]
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex{re, im}
+    }
+
+    /// `e^(i angle)`.
+    #[inline]
+    pub fn polar(angle: f64) -> Complex {
+        Complex{re: angle.cos(), im: angle.sin()}
+    }
+
+    #[inline]
+    pub fn conj(&self) -> Complex {
+        Complex{re: self.re, im: -self.im}
+    }
+
+    #[inline]
+    pub fn scale(&self, factor: f64) -> Complex {
+        Complex{re: self.re * factor, im: self.im * factor}
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    #[inline]
+    fn add(self, other: Complex) -> Complex {
+        Complex{re: self.re + other.re, im: self.im + other.im}
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    #[inline]
+    fn sub(self, other: Complex) -> Complex {
+        Complex{re: self.re - other.re, im: self.im - other.im}
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    #[inline]
+    fn mul(self, other: Complex) -> Complex {
+        Complex{re: self.re * other.re - self.im * other.im, im: self.re * other.im + self.im * other.re}
+    }
+}
+
+/// `roots[k + j]` is `e^(i pi j / k)` for powers of two `k < n` and `j < k`.
+///
+/// Every root comes from its own `cos` and `sin`, so errors do not pile up as with repeated multiplication.
+fn roots(n: usize) -> Vec<Complex> {
+    let mut roots = vec![Complex::new(1.0, 0.0); n.max(2)];
+    let mut k = 2;
+    while k < n {
+        for j in 0..k {
+            roots[k + j] = Complex::polar(PI * j as f64 / k as f64);
+        }
+        k *= 2;
+    }
+    roots
+}
+
+/// Unnormalized in-place discrete Fourier transform with the kernel `e^(2 pi i jk / n)`, the length a power of two.
+///
+/// The inverse transform is the same one with `a[1..]` reversed, divided by the length.
+#[inline]
+pub fn fft(a: &mut [Complex]) {
+    fft_with(a, &roots(a.len()));
+}
+
+/// `fft` with the table of `roots` for the length of `a`, so that several transforms can share it.
+fn fft_with(a: &mut [Complex], roots: &[Complex]) {
+    let n = a.len();
+    assert!(n.is_power_of_two(), "Length is not a power of two");
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let mut half = 1;
+    while half < n {
+        for chunk in a.chunks_mut(2 * half) {
+            let (left, right) = chunk.split_at_mut(half);
+            for k in 0..half {
+                let z = roots[half + k] * right[k];
+                right[k] = left[k] - z;
+                left[k] = left[k] + z;
+            }
+        }
+        half *= 2;
+    }
+}
+
+/// Convolution of real sequences by one forward and one inverse FFT, packing `b` into the imaginary parts.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::fft::convolution_f64;
+/// let a: Vec<f64> = (0..500).map(|i| ((i * 7919) % 1000) as f64 / 7.0 - 50.0).collect();
+/// let b: Vec<f64> = (0..300).map(|i| ((i * 104729) % 997) as f64 * 0.25).collect();
+/// let ans = convolution_f64(&a, &b);
+/// assert_eq!(ans.len(), 799);
+/// for k in 0..ans.len() {
+///     let naive: f64 = (0..a.len()).filter(|&i| i <= k && k - i < b.len()).map(|i| a[i] * b[k - i]).sum();
+///     assert!((ans[k] - naive).abs() < 1e-6);
+/// }
+/// assert!(convolution_f64(&[], &b).is_empty());
+/// ```
+pub fn convolution_f64(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    let mut packed = vec![Complex::default(); n];
+    for (i, &x) in a.iter().enumerate() {
+        packed[i].re = x;
+    }
+    for (i, &x) in b.iter().enumerate() {
+        packed[i].im = x;
+    }
+    let roots = roots(n);
+    fft_with(&mut packed, &roots);
+    // With c = a + ib the square c^2 has a^2 - b^2 + 2iab, and the conjugate terms cancel all but 4iab
+    let squares: Vec<Complex> = packed.iter().map(|&x| x * x).collect();
+    let mut out: Vec<Complex> = (0..n).map(|i| squares[(n - i) & (n - 1)] - squares[i].conj()).collect();
+    fft_with(&mut out, &roots);
+    out[..len].iter().map(|x| x.im / (4 * n) as f64).collect()
+}
+
+/// Exact convolution modulo any `modulus`, for inputs below `2^30`, split into 15-bit halves.
+///
+/// Four products of halves take two forward and two inverse FFTs. Rounding stays exact for results up to
+/// `2^19` long even with every input at `2^30 - 1`; at `2^20` such inputs already give wrong coefficients.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::fft::convolution_split;
+/// # use olympic_lib::shared::ntt::{convolution_arbitrary, convolution_exact};
+/// for &m in &[1_000_000_007, 999_999_937, 1 << 30, 2] {
+///     let a: Vec<u64> = (0..1000_u64).map(|i| (i * i * 1_000_003 + 999_999_999) % m).collect();
+///     let b: Vec<u64> = (0..777_u64).map(|i| (i * 31 + m - 1) % m).collect();
+///     let ans = convolution_split(&a, &b, m);
+///     assert_eq!(ans, convolution_arbitrary(&a, &b, m));
+///     for &k in &[0, 1, 500, 1000, 1775] {
+///         let naive = (0..a.len()).filter(|&i| i <= k && k - i < b.len())
+///             .fold(0, |acc, i| (acc + a[i] * b[k - i]) % m);
+///         assert_eq!(ans[k], naive);
+///     }
+/// }
+/// // Any modulus works once the inputs are below 2^30
+/// let a: Vec<u64> = (0..1000_u64).map(|i| (i * i * 1_000_003) % (1 << 30)).collect();
+/// for &m in &[u64::MAX, u64::MAX - 58, 1 << 50] {
+///     let exact = convolution_exact(&a, &a).into_iter().map(|x| (x % m as u128) as u64);
+///     assert_eq!(convolution_split(&a, &a, m), exact.collect::<Vec<_>>());
+/// }
+/// ```
+///
+/// Larger inputs panic instead of giving wrong products:
+///
+/// ```rust,should_panic
+/// # use olympic_lib::shared::fft::convolution_split;
+/// convolution_split(&[1 << 30], &[1], 998_244_353);
+/// ```
+pub fn convolution_split(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    const CUT: u64 = 1 << 15;
+    let len = a.len() + b.len() - 1;
+    let n = len.next_power_of_two();
+    let split = |x: &[u64]| {
+        let mut ans = vec![Complex::default(); n];
+        for (i, &v) in x.iter().enumerate() {
+            assert!(v < CUT * CUT, "Value is not below 2^30");
+            ans[i] = Complex::new((v / CUT) as f64, (v % CUT) as f64);
+        }
+        ans
+    };
+    let roots = roots(n);
+    let (mut left, mut right) = (split(a), split(b));
+    fft_with(&mut left, &roots);
+    fft_with(&mut right, &roots);
+    // Conjugate symmetry separates the halves of a; the results come out index-reversed for the inverse
+    let (mut outer, mut inner) = (vec![Complex::default(); n], vec![Complex::default(); n]);
+    let scale = 1.0 / (2 * n) as f64;
+    for i in 0..n {
+        let j = (n - i) & (n - 1);
+        outer[j] = ((left[i] + left[j].conj()) * right[i]).scale(scale);
+        let diff = ((left[i] - left[j].conj()) * right[i]).scale(scale);
+        // Divided by i
+        inner[j] = Complex::new(diff.im, -diff.re);
+    }
+    fft_with(&mut outer, &roots);
+    fft_with(&mut inner, &roots);
+    // Recombined in u128, as residues of a modulus near 2^64 overflow u64 when multiplied by CUT
+    let (modulus, cut) = (modulus as u128, CUT as u128);
+    let round = |x: f64| x.round().max(0.0) as u128 % modulus;
+    (0..len).map(|i| {
+        let high = round(outer[i].re);
+        let middle = (round(outer[i].im) + round(inner[i].re)) % modulus;
+        let low = round(inner[i].im);
+        (((high * cut + middle) % modulus * cut + low) % modulus) as u64
+    }).collect()
+}
@@ -0,0 +1,311 @@
+use shared::modint::{ModInt, Modulus};
+use shared::modular::sqrt_mod;
+use shared::ntt::{convolution_arbitrary, convolution_mod, NTT_PRIMES};
+
+// Polynomials and formal power series are coefficient vectors, lowest degree first.
+// Everything except `multiply` needs a prime modulus, and is fast for the NTT primes.
+
+/// Below this many points evaluation goes by Horner's rule.
+const HORNER_THRESHOLD: usize = 32;
+
+/// Product of polynomials, by one NTT for `NTT_PRIMES` and by three of them with the CRT otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::fps::multiply;
+/// # use olympic_lib::shared::modint::{ModInt1000000007, ModInt998244353};
+/// let a: Vec<ModInt998244353> = [1, 2, 3].iter().map(|&x| ModInt998244353::from(x)).collect();
+/// let b: Vec<ModInt998244353> = [-1, 1].iter().map(|&x| ModInt998244353::from(x)).collect();
+/// assert_eq!(multiply(&a, &b), [-1, -1, -1, 3].iter().map(|&x| ModInt998244353::from(x)).collect::<Vec<_>>());
+///
+/// let a: Vec<ModInt1000000007> = (0..100).map(|i| ModInt1000000007::from(-i)).collect();
+/// let square = multiply(&a, &a);
+/// assert_eq!(square[99], (0..100).map(|i| a[i] * a[99 - i]).sum());
+/// ```
+pub fn multiply<M: Modulus>(a: &[ModInt<M>], b: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    let modulus = M::modulus() as u64;
+    let raw = |x: &[ModInt<M>]| x.iter().map(|v| v.value() as u64).collect::<Vec<u64>>();
+    let product = if NTT_PRIMES.contains(&modulus) {
+        convolution_mod(&raw(a), &raw(b), modulus)
+    } else {
+        convolution_arbitrary(&raw(a), &raw(b), modulus)
+    };
+    product.into_iter().map(|v| ModInt::raw(v as u32)).collect()
+}
+
+/// First `n` coefficients of `a`, padded with zeros.
+fn prefix<M: Modulus>(a: &[ModInt<M>], n: usize) -> Vec<ModInt<M>> {
+    let mut ans = a[..a.len().min(n)].to_vec();
+    ans.resize(n, ModInt::default());
+    ans
+}
+
+fn trim<M: Modulus>(a: &mut Vec<ModInt<M>>) {
+    while a.last().is_some_and(|x| x.value() == 0) {
+        a.pop();
+    }
+}
+
+pub fn derivative<M: Modulus>(a: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    (1..a.len()).map(|i| a[i] * ModInt::from(i)).collect()
+}
+
+/// Antiderivative with zero constant term.
+pub fn integral<M: Modulus>(a: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    let p = M::modulus() as usize;
+    // inverses[i] = -(p / i) * inverses[p % i]
+    let mut inverses = vec![ModInt::from(1_u32); a.len() + 1];
+    for i in 2..=a.len() {
+        inverses[i] = -ModInt::from(p / i) * inverses[p % i];
+    }
+    let mut ans = Vec::with_capacity(a.len() + 1);
+    ans.push(ModInt::default());
+    ans.extend(a.iter().enumerate().map(|(i, &x)| x * inverses[i + 1]));
+    ans
+}
+
+/// First `n` coefficients of `1 / a` by Newton's iteration, needs `a[0] != 0`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::fps::{exp, inverse, log, multiply, pow, sqrt};
+/// # use olympic_lib::shared::modint::ModInt998244353;
+/// type Mint = ModInt998244353;
+/// let a: Vec<Mint> = (1..=200).map(|i| Mint::from(i * i + 7)).collect();
+/// let one = multiply(&a, &inverse(&a, 300));
+/// assert!(one[0] == Mint::from(1) && one[1..300].iter().all(|x| x.value() == 0));
+///
+/// // 1 / (1 - x - x^2) gives Fibonacci numbers
+/// let fib = inverse(&[Mint::from(1), Mint::from(-1), Mint::from(-1)], 10);
+/// assert_eq!(fib.iter().map(|x| x.value()).collect::<Vec<_>>(), vec![1, 1, 2, 3, 5, 8, 13, 21, 34, 55]);
+///
+/// // exp(x) = sum x^k / k!
+/// let e = exp(&[Mint::from(0), Mint::from(1)], 6);
+/// assert_eq!(e[5] * Mint::from(120), Mint::from(1));
+///
+/// let mut b = a.clone();
+/// b[0] = Mint::from(1);
+/// assert_eq!(exp(&log(&b, 200), 200), b);
+/// let cube = pow(&b, 3, 200);
+/// assert_eq!(cube, multiply(&multiply(&b, &b), &b)[..200].to_vec());
+/// let root = sqrt(&cube, 200).unwrap();
+/// assert_eq!(multiply(&root, &root)[..200].to_vec(), cube);
+///
+/// let shifted = [Mint::from(0), Mint::from(0), Mint::from(4), Mint::from(4), Mint::from(1)];
+/// assert_eq!(sqrt(&shifted, 4), Some(vec![Mint::from(0), Mint::from(2), Mint::from(1), Mint::from(0)]));
+/// assert_eq!(sqrt(&shifted[1..], 4), None);
+/// assert_eq!(pow(&shifted, 2, 5), vec![Mint::from(0), Mint::from(0), Mint::from(0), Mint::from(0), Mint::from(16)]);
+/// assert_eq!(pow(&shifted, 0, 2), vec![Mint::from(1), Mint::from(0)]);
+/// ```
+pub fn inverse<M: Modulus>(a: &[ModInt<M>], n: usize) -> Vec<ModInt<M>> {
+    assert!(a.first().is_some_and(|x| x.value() != 0), "Constant term is zero");
+    let mut ans = vec![a[0].inv()];
+    let mut len = 1;
+    while len < n {
+        len *= 2;
+        // g = g (2 - a g)
+        let mut correction = prefix(&multiply(&prefix(a, len), &ans), len);
+        for x in correction.iter_mut() {
+            *x = -*x;
+        }
+        correction[0] += ModInt::from(2_u32);
+        ans = prefix(&multiply(&ans, &correction), len);
+    }
+    ans.truncate(n);
+    ans
+}
+
+/// First `n` coefficients of `log a`, needs `a[0] == 1`.
+pub fn log<M: Modulus>(a: &[ModInt<M>], n: usize) -> Vec<ModInt<M>> {
+    assert!(a.first() == Some(&ModInt::from(1_u32)), "Constant term is not one");
+    if n == 0 {
+        return Vec::new();
+    }
+    let quotient = multiply(&derivative(&prefix(a, n)), &inverse(a, n));
+    integral(&quotient[..n - 1])
+}
+
+/// First `n` coefficients of `exp a` by Newton's iteration, needs `a[0] == 0`.
+pub fn exp<M: Modulus>(a: &[ModInt<M>], n: usize) -> Vec<ModInt<M>> {
+    assert!(a.first().map_or(true, |x| x.value() == 0), "Constant term is not zero");
+    let mut ans = vec![ModInt::from(1_u32)];
+    let mut len = 1;
+    while len < n {
+        len *= 2;
+        // f = f (1 - log f + a)
+        let mut correction = log(&ans, len);
+        for (x, &y) in correction.iter_mut().zip(&prefix(a, len)) {
+            *x = y - *x;
+        }
+        correction[0] += ModInt::from(1_u32);
+        ans = prefix(&multiply(&ans, &correction), len);
+    }
+    prefix(&ans, n)
+}
+
+/// First `n` coefficients of `a^k`.
+pub fn pow<M: Modulus>(a: &[ModInt<M>], k: u64, n: usize) -> Vec<ModInt<M>> {
+    let mut ans = vec![ModInt::default(); n];
+    if n == 0 {
+        return ans;
+    }
+    if k == 0 {
+        ans[0] = ModInt::from(1_u32);
+        return ans;
+    }
+    let start = match a.iter().position(|x| x.value() != 0) {
+        Some(start) if (start as u128) * (k as u128) < n as u128 => start,
+        _ => return ans,
+    };
+    // a = c x^start b with b[0] = 1, so a^k = c^k x^(start k) exp(k log b)
+    let shift = start * k as usize;
+    let lead = a[start];
+    let lead_inv = lead.inv();
+    let b: Vec<ModInt<M>> = a[start..].iter().map(|&x| x * lead_inv).collect();
+    let factor = ModInt::from(k);
+    let log_power: Vec<ModInt<M>> = log(&b, n - shift).into_iter().map(|x| x * factor).collect();
+    let lead_power = lead.pow(k);
+    for (i, x) in exp(&log_power, n - shift).into_iter().enumerate() {
+        ans[shift + i] = x * lead_power;
+    }
+    ans
+}
+
+/// First `n` coefficients of a square root of `a`, if there is one, with the smaller lowest coefficient.
+pub fn sqrt<M: Modulus>(a: &[ModInt<M>], n: usize) -> Option<Vec<ModInt<M>>> {
+    let mut ans = vec![ModInt::default(); n];
+    let start = match a.iter().position(|x| x.value() != 0) {
+        Some(start) => start,
+        None => return Some(ans),
+    };
+    if start % 2 == 1 {
+        return None;
+    }
+    let lead_root = ModInt::raw(sqrt_mod(a[start].value(), M::modulus())?);
+    let shift = start / 2;
+    if shift >= n {
+        return Some(ans);
+    }
+    let lead_inv = a[start].inv();
+    let b: Vec<ModInt<M>> = a[start..].iter().map(|&x| x * lead_inv).collect();
+    let half = ModInt::from(2_u32).inv();
+    let log_root: Vec<ModInt<M>> = log(&b, n - shift).into_iter().map(|x| x * half).collect();
+    for (i, x) in exp(&log_root, n - shift).into_iter().enumerate() {
+        ans[shift + i] = x * lead_root;
+    }
+    Some(ans)
+}
+
+/// Quotient and remainder of polynomials, panics on a zero divisor.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::fps::{div_rem, evaluate, interpolate, multiply};
+/// # use olympic_lib::shared::modint::ModInt998244353;
+/// type Mint = ModInt998244353;
+/// let a: Vec<Mint> = (0..300).map(|i| Mint::from(i * 31 % 17)).collect();
+/// let b: Vec<Mint> = (0..120).map(|i| Mint::from(i + 1)).collect();
+/// let (q, r) = div_rem(&a, &b);
+/// assert!(q.len() == 181 && r.len() < 120);
+/// let mut back = multiply(&b, &q);
+/// for (x, &y) in back.iter_mut().zip(&r) {
+///     *x += y;
+/// }
+/// assert_eq!(back, a);
+///
+/// let xs: Vec<Mint> = (0..100).map(|i| Mint::from(i * i + 3)).collect();
+/// let values = evaluate(&a, &xs);
+/// assert!(xs.iter().zip(&values).all(|(&x, &v)| a.iter().rev().fold(Mint::from(0), |acc, &c| acc * x + c) == v));
+/// let poly: Vec<Mint> = (0..100).map(|i| Mint::from(7 * i + 1)).collect();
+/// assert_eq!(interpolate(&xs, &evaluate(&poly, &xs)), poly);
+/// ```
+pub fn div_rem<M: Modulus>(a: &[ModInt<M>], b: &[ModInt<M>]) -> (Vec<ModInt<M>>, Vec<ModInt<M>>) {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    trim(&mut a);
+    trim(&mut b);
+    assert!(!b.is_empty(), "Division by zero");
+    if a.len() < b.len() {
+        return (Vec::new(), a);
+    }
+    // Reversed polynomials turn the quotient into a power series division
+    let len = a.len() - b.len() + 1;
+    let a_rev: Vec<ModInt<M>> = a.iter().rev().take(len).cloned().collect();
+    let b_rev: Vec<ModInt<M>> = b.iter().rev().cloned().collect();
+    let mut quotient = prefix(&multiply(&a_rev, &inverse(&b_rev, len)), len);
+    quotient.reverse();
+    let product = multiply(&b, &quotient);
+    let mut remainder: Vec<ModInt<M>> = (0..b.len() - 1).map(|i| a[i] - product[i]).collect();
+    trim(&mut remainder);
+    (quotient, remainder)
+}
+
+/// Products of `x - xs[i]` over the segments of a segment tree on the points.
+fn subproduct_tree<M: Modulus>(xs: &[ModInt<M>]) -> Vec<Vec<ModInt<M>>> {
+    fn build<M: Modulus>(tree: &mut Vec<Vec<ModInt<M>>>, xs: &[ModInt<M>], node: usize, l: usize, r: usize) {
+        tree[node] = if r - l == 1 {
+            vec![-xs[l], ModInt::from(1_u32)]
+        } else {
+            let mid = (l + r) / 2;
+            build(tree, xs, 2 * node, l, mid);
+            build(tree, xs, 2 * node + 1, mid, r);
+            multiply(&tree[2 * node], &tree[2 * node + 1])
+        };
+    }
+    let mut tree = vec![Vec::new(); 4 * xs.len()];
+    build(&mut tree, xs, 1, 0, xs.len());
+    tree
+}
+
+fn evaluate_on_tree<M: Modulus>(a: &[ModInt<M>], xs: &[ModInt<M>], tree: &[Vec<ModInt<M>>],
+                                node: usize, l: usize, r: usize, ans: &mut [ModInt<M>]) {
+    let reduced = div_rem(a, &tree[node]).1;
+    if r - l <= HORNER_THRESHOLD {
+        for i in l..r {
+            ans[i] = reduced.iter().rev().fold(ModInt::default(), |acc, &c| acc * xs[i] + c);
+        }
+        return;
+    }
+    let mid = (l + r) / 2;
+    evaluate_on_tree(&reduced, xs, tree, 2 * node, l, mid, ans);
+    evaluate_on_tree(&reduced, xs, tree, 2 * node + 1, mid, r, ans);
+}
+
+/// Values of `a` at all points by the remainder tree, in `O(n log^2 n)`.
+pub fn evaluate<M: Modulus>(a: &[ModInt<M>], xs: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    let mut ans = vec![ModInt::default(); xs.len()];
+    if !xs.is_empty() {
+        evaluate_on_tree(a, xs, &subproduct_tree(xs), 1, 0, xs.len(), &mut ans);
+    }
+    ans
+}
+
+/// The polynomial of degree below `n` through `n` points with distinct `xs`, in `O(n log^2 n)`.
+pub fn interpolate<M: Modulus>(xs: &[ModInt<M>], ys: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    assert_eq!(xs.len(), ys.len(), "Lengths differ");
+    if xs.is_empty() {
+        return Vec::new();
+    }
+    let tree = subproduct_tree(xs);
+    // Lagrange weights ys[i] / prod (xs[i] - xs[j]), the products being the derivative of the whole tree
+    let mut weights = vec![ModInt::default(); xs.len()];
+    evaluate_on_tree(&derivative(&tree[1]), xs, &tree, 1, 0, xs.len(), &mut weights);
+    for (w, &y) in weights.iter_mut().zip(ys) {
+        *w = y * w.checked_inv().expect("Points are not distinct");
+    }
+    fn combine<M: Modulus>(tree: &[Vec<ModInt<M>>], weights: &[ModInt<M>], node: usize, l: usize, r: usize)
+        -> Vec<ModInt<M>> {
+        if r - l == 1 {
+            return vec![weights[l]];
+        }
+        let mid = (l + r) / 2;
+        let left = multiply(&combine(tree, weights, 2 * node, l, mid), &tree[2 * node + 1]);
+        let right = multiply(&combine(tree, weights, 2 * node + 1, mid, r), &tree[2 * node]);
+        left.iter().zip(&right).map(|(&x, &y)| x + y).collect()
+    }
+    let mut ans = combine(&tree, &weights, 1, 0, xs.len());
+    trim(&mut ans);
+    ans
+}
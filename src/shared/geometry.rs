@@ -1,39 +1,25 @@
 use std::cmp::Ordering;
 use std::ops::{Mul, Sub, Add};
-
-const LOW_BITS: u64 = ((1 as u64) << 32) - 1;
-const HIGH_BITS: u64 = LOW_BITS << 32;
-fn big_prod(first: u64, second: u64) -> (u64, u64) {
-    let flow:   u64 = first & LOW_BITS;
-    let slow:   u64 = second & LOW_BITS;
-    let fhigh:  u64 = (first & HIGH_BITS) >> 32;
-    let shigh:  u64 = (second & HIGH_BITS) >> 32;
-    let reslow: u64 = flow * slow;
-    let resm1:  u64 = fhigh * slow;
-    let resm2:  u64 = flow * shigh;
-    let secdig: u64 = (resm1 & LOW_BITS) + (resm2 & LOW_BITS) + ((reslow & HIGH_BITS) >> 32);
-    (((secdig & HIGH_BITS) >> 32) + ((resm1 & HIGH_BITS) >> 32) + ((resm2 & HIGH_BITS) >> 32) + fhigh * shigh,
-     ((secdig & LOW_BITS) << 32) | (reslow & LOW_BITS))
-}
+use shared::bigints::big_prod;
 
 /// Radius-vector from the origin
 #[derive(Debug, Clone)]
-struct Point<T> {
-    x: T,
-    y: T,
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
     norm_sq: T,
 }
 
 impl<T: Copy + Mul<Output=T> + Add<Output=T>> Point<T> {
     #[inline]
-    fn new(x: T, y: T) -> Point<T> {
-        Point{x: x, y: y, norm_sq: x * x + y * y}
+    pub fn new(x: T, y: T) -> Point<T> {
+        Point{x, y, norm_sq: x * x + y * y}
     }
 }
 
 impl<T: PartialEq + PartialOrd + Default> Point<T> {
     #[inline]
-    fn bottom(&self) -> bool{
+    pub fn bottom(&self) -> bool{
         self.y < T::default() || (self.y == T::default() && self.x < T::default())
     }
 }
@@ -72,17 +58,17 @@ impl<T> PartialOrd for Point<T> where Point<T>: Ord, {
 }
 
 #[derive(Debug)]
-struct Angle<'a, T: 'a> {
-    v: &'a Point<T>,
-    w: &'a Point<T>,
+pub struct Angle<'a, T: 'a> {
+    pub v: &'a Point<T>,
+    pub w: &'a Point<T>,
     dot_product: T,
     norm_prod_sq: T,
 }
 
 impl<'a, T: Copy + Mul<Output=T>> Angle<'a, T> where &'a Point<T>: Mul<Output=T> {
     #[inline]
-    fn new(v: &'a Point<T>, w: &'a Point<T>) -> Angle<'a, T> {
-        Angle{v: v, w: w, dot_product: v * w, norm_prod_sq: v.norm_sq * w.norm_sq}
+    pub fn new(v: &'a Point<T>, w: &'a Point<T>) -> Angle<'a, T> {
+        Angle{v, w, dot_product: v * w, norm_prod_sq: v.norm_sq * w.norm_sq}
     }
 }
 
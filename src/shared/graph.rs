@@ -1,41 +1,71 @@
 use std::collections::VecDeque;
+use std::io::BufRead;
+use std::str::FromStr;
 
+use shared::grid::Grid;
+use shared::io::read_line;
 
-type Index2d = (usize, usize);
-type BoolMap = Vec<Vec<bool>>;
-type RectangleBfs<'a> = Bfs<'a, Index2d, BoolMap, RectangleGraph>;
 
-const MAXN: usize = 1000;
+pub type Index2d = (usize, usize);
+pub type BoolMap = Vec<Vec<bool>>;
+pub type RectangleBfs<'a> = Bfs<'a, Index2d, BoolMap, RectangleGraph>;
 
-struct RectangleGraph {
+pub const MAXN: usize = 1000;
+
+pub struct RectangleGraph {
     width: usize,
     height: usize,
     vertices: BoolMap,
 }
 
 impl RectangleGraph {
-    fn new<N: Copy, F>(vect: &Vec<Vec<N>>, closure: F) -> RectangleGraph
+    pub fn new<N: Copy, F>(vect: &[Vec<N>], closure: F) -> RectangleGraph
         where F: Fn(N) -> bool {
         let width = vect[0].len();
         let height = vect.len();
         let mut vertices: BoolMap = vec![Vec::with_capacity(height); width];
-        for y in 0..height {
-             for x in 0..width {
-                 vertices[x].push(closure(vect[y][x]));  // Swap x, y to make width 1st, height 2nd
+        for row in vect {
+             for (column, &cell) in vertices.iter_mut().zip(&row[..width]) {
+                 column.push(closure(cell));  // Swap x, y to make width 1st, height 2nd
             }
         }
-        RectangleGraph{width: width, height: height, vertices: vertices}
+        RectangleGraph{width, height, vertices}
+    }
+    /// Unlike `new`, cells of the grid are indexed `(row, col)`, while nodes stay `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use olympic_lib::shared::graph::RectangleGraph;
+    /// # use olympic_lib::shared::grid::Grid;
+    /// let mut input: &[u8] = b"..#\n##.\n";
+    /// let grid = Grid::read(&mut input, 2);
+    /// let g = RectangleGraph::from_grid(&grid, |&c| c == b'.');
+    /// assert_eq!(g.components(), vec![vec![(0, 0), (1, 0)], vec![(2, 1)]]);
+    /// ```
+    pub fn from_grid<N, F>(grid: &Grid<N>, closure: F) -> RectangleGraph
+        where F: Fn(&N) -> bool {
+        let vertices: BoolMap = grid.columns().map(|col| col.map(&closure).collect()).collect();
+        RectangleGraph{width: grid.width(), height: grid.height(), vertices}
+    }
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
     }
     #[inline]
-    fn bstate(&self, x: usize, y: usize) -> bool {  // Panic-dangerous!
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    #[inline]
+    pub fn bstate(&self, x: usize, y: usize) -> bool {  // Panic-dangerous!
         self.vertices[x][y]
     }
     #[inline]
-    fn ustate(&self, x: usize, y: usize) -> usize {  // Panic-dangerous!
+    pub fn ustate(&self, x: usize, y: usize) -> usize {  // Panic-dangerous!
         self.vertices[x][y] as usize
     }
     #[inline]
-    fn degrees(&self, node: Index2d) -> Index2d { // Active and non-active
+    pub fn degrees(&self, node: Index2d) -> Index2d { // Active and non-active
         let mut cnt: [usize; 2] = [0, 0];
         if node.0 + 1 != self.width {
             cnt[self.ustate(node.0 + 1, node.1)] += 1;
@@ -56,12 +86,12 @@ impl RectangleGraph {
     /// let g: RectangleGraph = RectangleGraph::new(&lab, |x: char| x == '.');
     /// { comps = g.components(); }  // Note the curly brackets!!
     #[inline]
-    fn components(&self) -> Vec<Vec<Index2d>> {
-        RectangleBfs::new(&self).components()
+    pub fn components(&self) -> Vec<Vec<Index2d>> {
+        RectangleBfs::new(self).components()
     }
 }
 
-trait Graph<N: PartialEq> {
+pub trait Graph<N: PartialEq> {
     fn neighbours(&self, node: N) -> Vec<N>;
     fn has_edge(&self, node1: N, node2: N) -> bool {
         for i in self.neighbours(node1){
@@ -92,7 +122,213 @@ impl Graph<Index2d> for RectangleGraph {
     }
 }
 
-trait VisitMap<N>{
+/// How edges are written in the input.
+///
+/// The default is the most common one: undirected and 1-indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeFormat {
+    pub directed: bool,
+    pub one_indexed: bool,
+}
+
+impl Default for EdgeFormat {
+    #[inline]
+    fn default() -> EdgeFormat {
+        EdgeFormat{directed: false, one_indexed: true}
+    }
+}
+
+impl EdgeFormat {
+    #[inline]
+    pub fn directed(self) -> EdgeFormat {
+        EdgeFormat{directed: true, ..self}
+    }
+    #[inline]
+    pub fn zero_indexed(self) -> EdgeFormat {
+        EdgeFormat{one_indexed: false, ..self}
+    }
+    #[inline]
+    fn vertex(&self, token: Option<&str>) -> usize {
+        let v: usize = token.expect("Edge is too short").parse().expect("Incorrect input format");
+        if self.one_indexed { v.checked_sub(1).expect("Vertices are 1-indexed") } else { v }
+    }
+}
+
+/// Unweighted graph on vertices `0..n`, stored as adjacency lists.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::graph::{AdjacencyList, EdgeFormat, Graph};
+/// let mut input: &[u8] = b"1 2\n2 3\n4 2\n";
+/// let g = AdjacencyList::read(&mut input, 4, 3, EdgeFormat::default());
+/// assert_eq!(g.neighbours(1), vec![0, 2, 3]);
+/// assert_eq!(g.bfs(3).collect::<Vec<_>>(), vec![3, 1, 0, 2]);
+///
+/// let mut input: &[u8] = b"0 1\n1 2\n";
+/// let g = AdjacencyList::read(&mut input, 3, 2, EdgeFormat::default().directed().zero_indexed());
+/// assert!(g.has_edge(0, 1) && !g.has_edge(1, 0));
+/// ```
+///
+/// A vertex `0` in 1-indexed input panics right away:
+///
+/// ```rust,should_panic
+/// # use olympic_lib::shared::graph::{AdjacencyList, EdgeFormat};
+/// AdjacencyList::read(&mut &b"0 1\n"[..], 2, 1, EdgeFormat::default());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdjacencyList {
+    adj: Vec<Vec<usize>>,
+}
+
+impl AdjacencyList {
+    #[inline]
+    pub fn new(n: usize) -> AdjacencyList {
+        AdjacencyList{adj: vec![Vec::new(); n]}
+    }
+    #[inline]
+    pub fn add_edge(&mut self, from: usize, to: usize, format: EdgeFormat) {
+        self.adj[from].push(to);
+        if !format.directed {
+            self.adj[to].push(from);
+        }
+    }
+    /// Reads `m` edges `u v`, one per line.
+    pub fn read<R: BufRead + ?Sized>(input: &mut R, n: usize, m: usize, format: EdgeFormat) -> AdjacencyList {
+        let mut graph = AdjacencyList::new(n);
+        let mut buffer = String::new();
+        for _ in 0..m {
+            read_line(input, &mut buffer, "read edge");
+            let mut list = buffer.split_whitespace();
+            let (from, to) = (format.vertex(list.next()), format.vertex(list.next()));
+            graph.add_edge(from, to, format);
+        }
+        graph
+    }
+    /// Reads the `n - 1` edges of a tree, one per line.
+    #[inline]
+    pub fn read_tree<R: BufRead + ?Sized>(input: &mut R, n: usize, format: EdgeFormat) -> AdjacencyList {
+        AdjacencyList::read(input, n, n.saturating_sub(1), format)
+    }
+    /// Reads a tree rooted at vertex `0`, given as one line with the parents of the other `n - 1` vertices.
+    ///
+    /// With `format.directed` the edges go from parents to children only.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use olympic_lib::shared::graph::{AdjacencyList, EdgeFormat, Graph};
+    /// let mut input: &[u8] = b"1 1 2\n";
+    /// let g = AdjacencyList::read_parents(&mut input, 4, EdgeFormat::default().directed());
+    /// assert_eq!((g.neighbours(0), g.neighbours(1), g.neighbours(3)), (vec![1, 2], vec![3], vec![]));
+    /// ```
+    pub fn read_parents<R: BufRead + ?Sized>(input: &mut R, n: usize, format: EdgeFormat) -> AdjacencyList {
+        let mut graph = AdjacencyList::new(n);
+        let mut buffer = String::new();
+        read_line(input, &mut buffer, "read parents");
+        let mut list = buffer.split_whitespace();
+        for child in 1..n {
+            let parent = format.vertex(list.next());
+            graph.add_edge(parent, child, format);
+        }
+        graph
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+    #[inline]
+    pub fn adjacent(&self, node: usize) -> &[usize] {
+        &self.adj[node]
+    }
+    /// Vertices reachable from `start` in BFS order.
+    #[inline]
+    pub fn bfs(&self, start: usize) -> Bfs<'_, usize, Vec<bool>, AdjacencyList> {
+        Bfs::with_start(self, vec![false; self.len()], start)
+    }
+}
+
+impl Graph<usize> for AdjacencyList {
+    #[inline]
+    fn neighbours(&self, node: usize) -> Vec<usize> {
+        self.adj[node].clone()
+    }
+}
+
+/// Graph on vertices `0..n` with edge weights, stored as adjacency lists of `(vertex, weight)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::graph::{EdgeFormat, Graph, WeightedGraph};
+/// let mut input: &[u8] = b"1 2 5\n3 1 -2\n";
+/// let g: WeightedGraph<i64> = WeightedGraph::read(&mut input, 3, 2, EdgeFormat::default());
+/// assert_eq!(g.edges(0), &[(1, 5), (2, -2)]);
+/// assert_eq!(g.neighbours(2), vec![0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedGraph<W> {
+    adj: Vec<Vec<(usize, W)>>,
+}
+
+impl<W: Copy> WeightedGraph<W> {
+    #[inline]
+    pub fn new(n: usize) -> WeightedGraph<W> {
+        WeightedGraph{adj: vec![Vec::new(); n]}
+    }
+    #[inline]
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W, format: EdgeFormat) {
+        self.adj[from].push((to, weight));
+        if !format.directed {
+            self.adj[to].push((from, weight));
+        }
+    }
+    /// Reads `m` edges `u v w`, one per line.
+    pub fn read<R: BufRead + ?Sized>(input: &mut R, n: usize, m: usize, format: EdgeFormat) -> WeightedGraph<W>
+        where W: FromStr {
+        let mut graph = WeightedGraph::new(n);
+        let mut buffer = String::new();
+        for _ in 0..m {
+            read_line(input, &mut buffer, "read edge");
+            let mut list = buffer.split_whitespace();
+            let (from, to) = (format.vertex(list.next()), format.vertex(list.next()));
+            let weight = list.next().expect("Edge is too short").parse().ok().expect("Incorrect input format");
+            graph.add_edge(from, to, weight, format);
+        }
+        graph
+    }
+    /// Reads the `n - 1` weighted edges of a tree, one per line.
+    #[inline]
+    pub fn read_tree<R: BufRead + ?Sized>(input: &mut R, n: usize, format: EdgeFormat) -> WeightedGraph<W>
+        where W: FromStr {
+        WeightedGraph::read(input, n, n.saturating_sub(1), format)
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+    #[inline]
+    pub fn edges(&self, node: usize) -> &[(usize, W)] {
+        &self.adj[node]
+    }
+}
+
+impl<W> Graph<usize> for WeightedGraph<W> {
+    #[inline]
+    fn neighbours(&self, node: usize) -> Vec<usize> {
+        self.adj[node].iter().map(|&(to, _)| to).collect()
+    }
+}
+
+pub trait VisitMap<N>{
     /// Mark `a` as visited.
     ///
     /// Return **true** if this is the first visist, false otherwise.
@@ -116,24 +352,50 @@ impl VisitMap<Index2d> for BoolMap {
 
 }
 
-struct Bfs<'a, N, VM, G: 'a> {
+impl VisitMap<usize> for Vec<bool> {
+    #[inline]
+    fn visit(&mut self, a: usize) -> bool {
+        let ans = self[a];
+        self[a] = true;
+        ans
+    }
+    #[inline]
+    fn is_visited(&self, a: &usize) -> bool {
+        self[*a]
+    }
+}
+
+pub struct Bfs<'a, N, VM, G: 'a> {
     queue: VecDeque<N>,
     discovered: VM,
     graph: &'a G,
 }
 
+impl<'a, N, VM: VisitMap<N>, G> Bfs<'a, N, VM, G> {
+    /// Starts from `start` with the vertices already marked in `discovered` excluded.
+    #[inline]
+    pub fn with_start(graph: &'a G, mut discovered: VM, start: N) -> Bfs<'a, N, VM, G>
+        where N: Copy {
+        let mut queue = VecDeque::new();
+        if !discovered.visit(start) {
+            queue.push_back(start);
+        }
+        Bfs{queue, discovered, graph}
+    }
+}
+
 impl<'a> RectangleBfs<'a> {
     #[inline]
-    fn new(graph: &'a RectangleGraph) -> RectangleBfs<'a> {
+    pub fn new(graph: &'a RectangleGraph) -> RectangleBfs<'a> {
         Bfs{queue:      VecDeque::new(),
             discovered: vec![vec![false; MAXN]; MAXN],
-            graph:      graph}
+            graph}
     }
     #[inline]
-    fn spawn(&mut self, node: Index2d) {
+    pub fn spawn(&mut self, node: Index2d) {
         self.queue.push_back(node)
     }
-    fn components(&mut self) -> Vec<Vec<Index2d>> {
+    pub fn components(&mut self) -> Vec<Vec<Index2d>> {
         let mut ans: Vec<Vec<Index2d>> = Vec::new();
         self.queue.clear();
         for x in 0..self.graph.width {
@@ -154,14 +416,12 @@ impl<'a, N, VM, G> Iterator for Bfs<'a, N, VM, G>
           G: Graph<N> {
     type Item = N;
     fn next(&mut self) -> Option<N> {
-        while let Some(node) = self.queue.pop_front() {
-            for succ in self.graph.neighbours(node) {
-                if !self.discovered.visit(succ) {
-                    self.queue.push_back(succ);
-                }
+        let node = self.queue.pop_front()?;
+        for succ in self.graph.neighbours(node) {
+            if !self.discovered.visit(succ) {
+                self.queue.push_back(succ);
             }
-            return Some(node);
         }
-        None
+        Some(node)
     }
 }
\ No newline at end of file
@@ -0,0 +1,239 @@
+use std::io::BufRead;
+use std::iter::{repeat, repeat_with, StepBy};
+use std::ops::{Index, IndexMut};
+use std::slice::Iter;
+
+use shared::graph::Index2d;
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBOURS8: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1),
+                                          (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// Rectangular table stored flat in row-major order and indexed by `(row, col)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::grid::Grid;
+/// let mut input: &[u8] = b"#..\n.#.\n";
+/// let mut g = Grid::read_with(&mut input, 2, |c| c == b'#');
+/// assert_eq!((g.height(), g.width()), (2, 3));
+/// assert!(g[(1, 1)] && !g[(0, 1)]);
+/// g[(0, 2)] = true;
+/// assert_eq!(g.neighbours((0, 1)).collect::<Vec<_>>(), vec![(0, 0), (0, 2), (1, 1)]);
+/// assert_eq!(g.column(2).filter(|&&c| c).count(), 1);
+///
+/// let flat = Grid::new(3, 0, 0_u8);
+/// assert_eq!(flat.rows().collect::<Vec<_>>(), vec![&[][..]; 3]);
+/// let empty = Grid::new(0, 2, 0_u8);
+/// assert_eq!((empty.rows().count(), empty.column(1).count(), empty.transpose().height()), (0, 0, 2));
+/// ```
+///
+/// Indexing past the end of a row panics instead of reading the next row, and so do ragged rows:
+///
+/// ```rust,should_panic
+/// # use olympic_lib::shared::grid::Grid;
+/// let g = Grid::new(2, 3, 0_u8);
+/// let _ = g[(0, 3)];
+/// ```
+///
+/// ```rust,should_panic
+/// # use olympic_lib::shared::grid::Grid;
+/// Grid::read(&mut &b"..\n.\n"[..], 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    #[inline]
+    pub fn new(height: usize, width: usize, value: T) -> Grid<T> where T: Clone {
+        Grid{height, width, cells: vec![value; height * width]}
+    }
+
+    /// Panics if `cells` does not hold exactly `height * width` items.
+    #[inline]
+    pub fn from_vec(height: usize, width: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), height * width, "Grid size mismatch");
+        Grid{height, width, cells}
+    }
+
+    /// Builds a grid from rows of equal length, e.g. the output of `read2dchar!`.
+    pub fn from_rows<N: Copy, F>(rows: &[Vec<N>], closure: F) -> Grid<T>
+        where F: Fn(N) -> T {
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(rows.len() * width);
+        for row in rows {
+            assert_eq!(row.len(), width, "Grid rows have different lengths");
+            cells.extend(row.iter().map(|&cell| closure(cell)));
+        }
+        Grid{height: rows.len(), width, cells}
+    }
+
+    /// Reads `height` lines of `input` and maps every byte to a cell.
+    ///
+    /// Line endings are dropped. Panics if the rows have different lengths.
+    pub fn read_with<R: BufRead + ?Sized, F>(input: &mut R, height: usize, closure: F) -> Grid<T>
+        where F: Fn(u8) -> T {
+        let mut lines = Vec::with_capacity(height);
+        let mut buffer = Vec::new();
+        for _ in 0..height {
+            buffer.clear();
+            input.read_until(b'\n', &mut buffer).expect("Failed to read grid");
+            while let Some(&b'\n') | Some(&b'\r') = buffer.last() {
+                buffer.pop();
+            }
+            lines.push(buffer.clone());
+        }
+        let width = lines.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(height * width);
+        for line in &lines {
+            assert_eq!(line.len(), width, "Grid rows have different lengths");
+            cells.extend(line.iter().map(|&byte| closure(byte)));
+        }
+        Grid{height, width, cells}
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn contains(&self, pos: Index2d) -> bool {
+        pos.0 < self.height && pos.1 < self.width
+    }
+
+    #[inline]
+    pub fn get(&self, pos: Index2d) -> Option<&T> {
+        if self.contains(pos) { Some(&self.cells[pos.0 * self.width + pos.1]) } else { None }
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, pos: Index2d) -> Option<&mut T> {
+        if self.contains(pos) { Some(&mut self.cells[pos.0 * self.width + pos.1]) } else { None }
+    }
+
+    #[inline]
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// All `height` rows, empty ones too if the width is zero.
+    #[inline]
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Chunks of an empty slice are none at all, so zero-width rows are made up
+        self.cells.chunks(self.width.max(1)).chain(repeat(&[][..])).take(self.height)
+    }
+
+    #[inline]
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let height = self.height;
+        self.cells.chunks_mut(self.width.max(1)).chain(repeat_with(|| &mut [][..])).take(height)
+    }
+
+    /// Panics if `col` is out of range.
+    #[inline]
+    pub fn column(&self, col: usize) -> StepBy<Iter<'_, T>> {
+        assert!(col < self.width, "Grid column out of range");
+        // With zero height there are no cells to start from
+        self.cells[col.min(self.cells.len())..].iter().step_by(self.width)
+    }
+
+    #[inline]
+    pub fn columns(&self) -> impl Iterator<Item = StepBy<Iter<'_, T>>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Positions of all cells in row-major order.
+    #[inline]
+    pub fn positions(&self) -> impl Iterator<Item = Index2d> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Side-adjacent positions of `pos` which lie inside the grid.
+    #[inline]
+    pub fn neighbours(&self, pos: Index2d) -> impl Iterator<Item = Index2d> {
+        self.shifted(pos, &NEIGHBOURS4)
+    }
+
+    /// Side- and corner-adjacent positions of `pos` which lie inside the grid.
+    #[inline]
+    pub fn neighbours8(&self, pos: Index2d) -> impl Iterator<Item = Index2d> {
+        self.shifted(pos, &NEIGHBOURS8)
+    }
+
+    fn shifted(&self, pos: Index2d, shifts: &'static [(isize, isize)]) -> impl Iterator<Item = Index2d> {
+        let (height, width) = (self.height, self.width);
+        shifts.iter()
+            .map(move |&(dr, dc)| (pos.0.wrapping_add(dr as usize), pos.1.wrapping_add(dc as usize)))
+            .filter(move |&(row, col)| row < height && col < width)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    #[inline]
+    pub fn map<U, F>(&self, closure: F) -> Grid<U>
+        where F: FnMut(&T) -> U {
+        Grid{height: self.height, width: self.width, cells: self.cells.iter().map(closure).collect()}
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Grid<T> where T: Clone {
+        let cells = self.columns().flat_map(|col| col.cloned()).collect();
+        Grid{height: self.width, width: self.height, cells}
+    }
+}
+
+impl Grid<u8> {
+    /// Reads `height` lines of `input` as raw bytes, see `read_with`.
+    #[inline]
+    pub fn read<R: BufRead + ?Sized>(input: &mut R, height: usize) -> Grid<u8> {
+        Grid::read_with(input, height, |byte| byte)
+    }
+
+    /// Position of the first cell equal to `byte`, in row-major order.
+    #[inline]
+    pub fn find(&self, byte: u8) -> Option<Index2d> {
+        self.cells.iter().position(|&c| c == byte).map(|i| (i / self.width, i % self.width))
+    }
+}
+
+impl<T> Index<Index2d> for Grid<T> {
+    type Output = T;
+    #[inline]
+    fn index(&self, pos: Index2d) -> &T {
+        assert!(self.contains(pos), "Grid index out of range");
+        &self.cells[pos.0 * self.width + pos.1]
+    }
+}
+
+impl<T> IndexMut<Index2d> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, pos: Index2d) -> &mut T {
+        assert!(self.contains(pos), "Grid index out of range");
+        &mut self.cells[pos.0 * self.width + pos.1]
+    }
+}
@@ -0,0 +1,236 @@
+use std::convert::TryFrom;
+
+/// Primitive integers of every width, handled through their sign and `u128` magnitude.
+pub trait PrimitiveInteger: Copy {
+    /// Whether the value is negative, and its absolute value.
+    fn to_parts(self) -> (bool, u128);
+    /// `None` if the value does not fit.
+    fn from_parts(negative: bool, magnitude: u128) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => ($(
+        impl PrimitiveInteger for $t {
+            #[inline]
+            fn to_parts(self) -> (bool, u128) {
+                (false, self as u128)
+            }
+
+            #[inline]
+            fn from_parts(negative: bool, magnitude: u128) -> Option<$t> {
+                if negative && magnitude != 0 { None } else { <$t>::try_from(magnitude).ok() }
+            }
+        }
+    )*);
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => ($(
+        impl PrimitiveInteger for $t {
+            #[inline]
+            fn to_parts(self) -> (bool, u128) {
+                (self < 0, (self as i128).unsigned_abs())
+            }
+
+            #[inline]
+            fn from_parts(negative: bool, magnitude: u128) -> Option<$t> {
+                let value = if !negative {
+                    i128::try_from(magnitude).ok()?
+                } else if magnitude == 0 {
+                    0
+                } else {
+                    // Goes through magnitude - 1, so that the minimum of i128 fits
+                    -i128::try_from(magnitude - 1).ok()? - 1
+                };
+                <$t>::try_from(value).ok()
+            }
+        }
+    )*);
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+#[inline]
+fn positive<T: PrimitiveInteger>(n: T) -> u128 {
+    let (negative, magnitude) = n.to_parts();
+    assert!(!negative && magnitude > 0, "Logarithm of a non-positive number");
+    magnitude
+}
+
+/// Exact `floor(log2(n))` for `n > 0`.
+///
+/// Unlike `(n as f64).log2() as u32`, this is never off by one next to powers of two.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::integers::{ilog, ilog2};
+/// assert_eq!((ilog2(1_u8), ilog2(255_u8), ilog2(i64::MAX), ilog2(u128::MAX)), (0, 7, 62, 127));
+/// for e in 1..64 {
+///     assert_eq!((ilog2(1_u64 << e), ilog2((1_u64 << e) - 1)), (e, e - 1));
+/// }
+/// // The float formula gives 54 here, as 2^54 - 1 rounds up to 2^54
+/// let y: i64 = (1 << 54) - 1;
+/// assert_eq!((ilog2(y), ((y as f64).log2() as u32)), (53, 54));
+///
+/// assert_eq!((ilog(1_u32, 10), ilog(999_u32, 10), ilog(1000_u32, 10)), (0, 2, 3));
+/// assert_eq!((ilog(u64::MAX, 10), ilog(10_000_000_000_000_000_000_u64, 10)), (19, 19));
+/// assert_eq!((ilog(3_i32.pow(19) - 1, 3), ilog(3_i32.pow(19), 3), ilog(i32::MAX, i32::MAX)), (18, 19, 1));
+/// for base in 2..300_u64 {
+///     let mut power = base;
+///     for k in 1.. {
+///         assert_eq!((ilog(power - 1, base), ilog(power, base), ilog(power + 1, base)), (k - 1, k, k));
+///         match power.checked_mul(base) {
+///             Some(next) => power = next,
+///             None => {
+///                 assert_eq!(ilog(u64::MAX, base), k);
+///                 break;
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[inline]
+pub fn ilog2<T: PrimitiveInteger>(n: T) -> u32 {
+    127 - positive(n).leading_zeros()
+}
+
+/// Exact `floor(log(n) / log(base))` for `n > 0` and `base >= 2`.
+pub fn ilog<T: PrimitiveInteger>(n: T, base: T) -> u32 {
+    let n = positive(n);
+    let (negative, base) = base.to_parts();
+    assert!(!negative && base >= 2, "Logarithm base below 2");
+    let (mut ans, mut power) = (0, base);
+    while power <= n {
+        ans += 1;
+        power = match power.checked_mul(base) {
+            Some(power) => power,
+            None => break,
+        };
+    }
+    ans
+}
+
+/// Floor of the `k`-th root of a magnitude.
+fn root_u128(n: u128, k: u32) -> u128 {
+    if n < 2 || k == 1 {
+        return n;
+    }
+    if k >= 128 {
+        return 1;
+    }
+    // Newton's iteration decreases from any start above the root and stops right at its floor
+    let bits = 128 - n.leading_zeros();
+    let mut x: u128 = 1 << ((bits + k - 1) / k);
+    loop {
+        let quotient = x.checked_pow(k - 1).map_or(0, |power| n / power);
+        let next = ((k - 1) as u128 * x + quotient) / k as u128;
+        if next >= x {
+            break;
+        }
+        x = next;
+    }
+    while x.checked_pow(k).map_or(true, |power| power > n) {
+        x -= 1;
+    }
+    while (x + 1).checked_pow(k).is_some_and(|power| power <= n) {
+        x += 1;
+    }
+    x
+}
+
+/// Exact `floor(n^(1/k))` for `k > 0`, negative `n` allowed only for odd `k`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::integers::{icbrt, iroot, isqrt};
+/// assert_eq!((isqrt(0_u8), isqrt(255_u8), isqrt(u64::MAX), isqrt(u128::MAX)), (0, 15, 4_294_967_295, u64::MAX as u128));
+/// assert_eq!((isqrt(i64::MAX), icbrt(u64::MAX), icbrt(i64::MIN)), (3_037_000_499, 2_642_245, -2_097_152));
+/// for x in (1..1000_u64).chain(4_294_967_000..4_294_967_296) {
+///     assert_eq!((isqrt(x * x), isqrt(x * x - 1), isqrt(x * x + 2 * x)), (x, x - 1, x));
+/// }
+/// for x in (1..1000_i64).chain(2_097_000..2_097_152) {
+///     assert_eq!((icbrt(x * x * x), icbrt(x * x * x - 1), icbrt(-x * x * x), icbrt(-x * x * x - 1)), (x, x - 1, -x, -x - 1));
+/// }
+/// // Floor, not truncation, for negative numbers
+/// assert_eq!((icbrt(-9_i32), icbrt(-8_i32), iroot(-33_i32, 5)), (-3, -2, -3));
+///
+/// assert_eq!((iroot(u128::MAX, 2), iroot(u128::MAX, 127), iroot(u128::MAX, 128)), (u64::MAX as u128, 2, 1));
+/// assert_eq!((iroot(10_u64.pow(18), 6), iroot(10_u64.pow(18) - 1, 6), iroot(7_u8, 1)), (1000, 999, 7));
+/// for k in 1..70 {
+///     for n in (0..5000_u64).chain(u64::MAX - 5000..=u64::MAX) {
+///         let r = iroot(n, k);
+///         assert!((r as u128).pow(k) <= n as u128 && (r as u128 + 1).pow(k) > n as u128);
+///     }
+/// }
+/// ```
+pub fn iroot<T: PrimitiveInteger>(n: T, k: u32) -> T {
+    assert!(k > 0, "Zeroth root");
+    let (negative, magnitude) = n.to_parts();
+    assert!(!negative || k % 2 == 1, "Even root of a negative number");
+    let root = root_u128(magnitude, k);
+    let ans = if negative && root.checked_pow(k) != Some(magnitude) { root + 1 } else { root };
+    T::from_parts(negative, ans).unwrap()
+}
+
+/// Exact `floor(sqrt(n))` for `n >= 0`.
+#[inline]
+pub fn isqrt<T: PrimitiveInteger>(n: T) -> T {
+    iroot(n, 2)
+}
+
+/// Exact `floor(cbrt(n))`, also for negative `n`.
+#[inline]
+pub fn icbrt<T: PrimitiveInteger>(n: T) -> T {
+    iroot(n, 3)
+}
+
+/// `ceil(a / b)` for any signs, panics if `b` is zero or the quotient overflows.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::integers::ceil_div;
+/// assert_eq!((ceil_div(7, 2), ceil_div(-7, 2), ceil_div(7, -2), ceil_div(-7, -2)), (4, -3, -3, 4));
+/// assert_eq!((ceil_div(6, 3), ceil_div(-6, 3), ceil_div(0, -5)), (2, -2, 0));
+/// assert_eq!((ceil_div(u64::MAX, 2), ceil_div(u64::MAX, u64::MAX)), (1 << 63, 1));
+/// assert_eq!((ceil_div(i64::MIN, 2), ceil_div(i64::MIN + 1, -1)), (i64::MIN / 2, i64::MAX));
+/// for a in -50..50_i8 {
+///     for b in (-10..10_i8).filter(|&b| b != 0) {
+///         assert_eq!(ceil_div(a, b) as f64, (a as f64 / b as f64).ceil());
+///     }
+/// }
+/// ```
+pub fn ceil_div<T: PrimitiveInteger>(a: T, b: T) -> T {
+    let ((a_negative, a), (b_negative, b)) = (a.to_parts(), b.to_parts());
+    assert!(b != 0, "Division by zero");
+    let (quotient, remainder) = (a / b, a % b);
+    let ans = if a_negative == b_negative {
+        T::from_parts(false, quotient + (remainder != 0) as u128)
+    } else {
+        T::from_parts(true, quotient)
+    };
+    ans.expect("Quotient overflows")
+}
+
+/// `base^exp`, or `None` if it overflows `T`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::integers::checked_pow;
+/// assert_eq!((checked_pow(2_i64, 62), checked_pow(2_i64, 63), checked_pow(-2_i64, 63)), (Some(1 << 62), None, Some(i64::MIN)));
+/// assert_eq!((checked_pow(10_u64, 19), checked_pow(10_u64, 20), checked_pow(0_u8, 0)), (Some(10_000_000_000_000_000_000), None, Some(1)));
+/// assert_eq!((checked_pow(-1_i8, 1_000_001), checked_pow(1_u128, u32::MAX), checked_pow(3_u128, 81)), (Some(-1), Some(1), None));
+/// for base in -20..20_i16 {
+///     for exp in 0..20 {
+///         assert_eq!(checked_pow(base, exp), base.checked_pow(exp));
+///     }
+/// }
+/// ```
+pub fn checked_pow<T: PrimitiveInteger>(base: T, exp: u32) -> Option<T> {
+    let (negative, magnitude) = base.to_parts();
+    T::from_parts(negative && exp % 2 == 1, magnitude.checked_pow(exp)?)
+}
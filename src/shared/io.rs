@@ -0,0 +1,153 @@
+use std::cmp::min;
+use std::fmt::Display;
+use std::io::{stdin, stdout, BufRead, BufWriter, Read, Result, StdoutLock, Write};
+
+/// Reads one line from `input` into an emptied `buffer`.
+///
+/// Panics with `what` in the message if the reader fails.
+#[inline]
+pub fn read_line<R: BufRead + ?Sized>(input: &mut R, buffer: &mut String, what: &str) {
+    buffer.clear();
+    input.read_line(buffer).unwrap_or_else(|_| panic!("Failed to {}", what));
+}
+
+/// Writes the items of `slice` on one line, separated by spaces.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::io::write_slice;
+/// let mut output = Vec::new();
+/// write_slice(&mut output, &[1, 2, 3]);
+/// assert_eq!(output, b"1 2 3\n");
+/// ```
+pub fn write_slice<W: Write, T: Display>(output: &mut W, slice: &[T]) {
+    for (i, item) in slice.iter().enumerate() {
+        if i != 0 {
+            write!(output, " ").expect("Failed to write_slice");
+        }
+        write!(output, "{}", item).expect("Failed to write_slice");
+    }
+    writeln!(output).expect("Failed to write_slice");
+}
+
+/// Stdin read one line at a time, locking it only while the line is taken.
+///
+/// Stdin is never held locked in between, so the stdin forms of the reading macros may still be used
+/// next to this reader, as long as both read whole lines.
+#[derive(Debug, Default)]
+pub struct StdinReader {
+    line: Vec<u8>,
+    pos: usize,
+}
+
+impl StdinReader {
+    #[inline]
+    pub fn new() -> StdinReader {
+        StdinReader::default()
+    }
+}
+
+impl Read for StdinReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let len = {
+            let available = self.fill_buf()?;
+            let len = min(available.len(), buf.len());
+            buf[..len].copy_from_slice(&available[..len]);
+            len
+        };
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for StdinReader {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.pos == self.line.len() {
+            self.line.clear();
+            self.pos = 0;
+            stdin().lock().read_until(b'\n', &mut self.line)?;
+        }
+        Ok(&self.line[self.pos..])
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos = min(self.pos + amt, self.line.len());
+    }
+}
+
+/// Runs `solve` on a `StdinReader` and a buffered, locked stdout.
+///
+/// A solution written as `fn solve<R: BufRead, W: Write>(input: &mut R, output: &mut W)`
+/// is started from `main` with `run(solve)` and stays callable from tests.
+pub fn run<F>(solve: F)
+    where F: FnOnce(&mut StdinReader, &mut BufWriter<StdoutLock<'static>>) {
+    let mut input = StdinReader::new();
+    let mut output = BufWriter::new(stdout().lock());
+    solve(&mut input, &mut output);
+    output.flush().expect("Failed to flush stdout");
+}
+
+/// Runs `solve` on the in-memory `input` and returns everything it wrote.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate olympic_lib;
+/// # use olympic_lib::shared::io::run_on;
+/// # use std::io::{BufRead, Write};
+/// fn solve<R: BufRead, W: Write>(input: &mut R, output: &mut W) {
+///     readln!(input; a: i64, b: i64);
+///     writeln!(output, "{}", a + b).unwrap();
+/// }
+/// # fn main() {
+/// assert_eq!(run_on(solve, "2 3\n"), "5\n");
+/// # }
+/// ```
+pub fn run_on<'a, F>(solve: F, input: &'a str) -> String
+    where F: FnOnce(&mut &'a [u8], &mut Vec<u8>) {
+    let mut output = Vec::new();
+    solve(&mut input.as_bytes(), &mut output);
+    String::from_utf8(output).expect("Solution output is not UTF-8")
+}
+
+/// Compares two outputs the way judges usually do.
+///
+/// Trailing whitespace on every line and trailing empty lines are ignored.
+pub fn outputs_match(actual: &str, expected: &str) -> bool {
+    let actual: Vec<&str> = actual.trim_end().lines().map(str::trim_end).collect();
+    let expected: Vec<&str> = expected.trim_end().lines().map(str::trim_end).collect();
+    actual == expected
+}
+
+/// Runs `solve` on `input` and panics unless its output matches `expected`.
+///
+/// See `outputs_match` for how the outputs are compared.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate olympic_lib;
+/// # use olympic_lib::shared::io::assert_solution;
+/// # use std::io::{BufRead, Write};
+/// fn solve<R: BufRead, W: Write>(input: &mut R, output: &mut W) {
+///     readln!(input; n: usize);
+///     readvec!(input; a: i64);
+///     a.sort();
+///     writeln!(output, "{} {}", a[0], a[n - 1]).unwrap();
+/// }
+/// # fn main() {
+/// assert_solution(solve, "3\n5 -1 2\n", "-1 5");
+/// # }
+/// ```
+pub fn assert_solution<'a, F>(solve: F, input: &'a str, expected: &str)
+    where F: FnOnce(&mut &'a [u8], &mut Vec<u8>) {
+    let actual = run_on(solve, input);
+    if !outputs_match(&actual, expected) {
+        panic!("Wrong answer\n--- input ---\n{}\n--- expected ---\n{}\n--- actual ---\n{}",
+               input.trim_end(), expected.trim_end(), actual.trim_end());
+    }
+}
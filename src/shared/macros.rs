@@ -1,5 +1,7 @@
 /// Reads a finite number of input tokens from stdin, divided by spaces.
 ///
+/// An optional `&mut` reference to a `BufRead` before a semicolon is read instead of stdin,
+/// e.g. the reader `io::run` passes to the solution.
 /// The variables become immutable to supress Rust warnings.
 /// Note that the implementation is suboptimal for reading only 1 item,
 /// but it is left such for the sake of clarity.
@@ -14,11 +16,24 @@
 /// # }
 /// ```
 /// Given an input `12 3.4 hello`, sets `x = 12, y = 3.4, z = "hello"`.
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate olympic_lib;
+/// # fn main() {
+/// let input = &mut &b"12 3.4 hello\n"[..];
+/// readln!(input; x: i64, y: f64, z: String);
+/// assert_eq!((x, y, z.as_str()), (12, 3.4, "hello"));
+/// # }
+/// ```
 #[macro_export]
 macro_rules! readln {
     ( $( $x:ident: $t:ty ),* ) => (
+        readln!(&mut std::io::stdin().lock(); $( $x: $t ),*);
+    );
+    ($input:expr; $( $x:ident: $t:ty ),* ) => (
         let mut buffer = String::new();
-        std::io::stdin().read_line(&mut buffer).expect("Failed to readln!");
+        std::io::BufRead::read_line(&mut *$input, &mut buffer).expect("Failed to readln!");
         let mut list = buffer.trim().split(" ");
         $(
             let $x: $t = list.next().unwrap().parse().expect("Incorrect input format");
@@ -29,6 +44,7 @@ macro_rules! readln {
 /// Reads a vector of given type from stdin, using spaces as separators.
 ///
 /// Unlike readln!, the resulting Vec is mutable.
+/// Like `readln!`, takes an optional reader before a semicolon.
 ///
 /// # Examples
 ///
@@ -43,8 +59,11 @@ macro_rules! readln {
 #[macro_export]
 macro_rules! readvec {
     ($x:ident: $t:ty) => (
+        readvec!(&mut std::io::stdin().lock(); $x: $t);
+    );
+    ($input:expr; $x:ident: $t:ty) => (
         let mut buffer = String::new();
-        std::io::stdin().read_line(&mut buffer).expect("Failed to readvec!");
+        std::io::BufRead::read_line(&mut *$input, &mut buffer).expect("Failed to readvec!");
         let mut $x = buffer.trim().split(" ").map(|x| x.parse().unwrap()).collect::<Vec<$t>>();
     );
 }
@@ -54,6 +73,8 @@ macro_rules! readvec {
 /// Separating '\n' signs are dropped.
 /// The resulting vector is mutable.
 /// Width is used only to set initial Vec capacity appropriately.
+/// Prefer `readgrid!`, which stores bytes in a flat `Grid`.
+/// Like `readln!`, takes an optional reader before a semicolon.
 ///
 /// # Examples
 ///
@@ -69,12 +90,80 @@ macro_rules! readvec {
 #[macro_export]
 macro_rules! read2dchar {
     ($width:expr, $height:expr, $ans:ident) => (
+        read2dchar!(&mut std::io::stdin().lock(); $width, $height, $ans);
+    );
+    ($input:expr; $width:expr, $height:expr, $ans:ident) => (
         let mut $ans = vec![Vec::with_capacity($width); $height];
         let mut buffer = String::new();
         for i in 0..$height {
-            std::io::stdin().read_line(&mut buffer).expect("Failed to read2dchar!");
+            std::io::BufRead::read_line(&mut *$input, &mut buffer).expect("Failed to read2dchar!");
             $ans[i] = buffer.trim().chars().collect(); // TODO: Remove index checking!
             buffer.clear();
         }
     );
+}
+
+/// Reads a `Grid` of `height` lines from stdin, one byte per cell.
+///
+/// Like `readln!`, takes an optional reader before a semicolon.
+/// An optional closure maps every byte to a cell.
+/// Panics on rows of different lengths, see `Grid::read_with`.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate olympic_lib;
+/// # fn main() {
+/// let input = &mut &b".*\n**\n..\n"[..];
+/// readgrid!(input; 3, g, |c| c == b'*');
+/// assert_eq!(g.rows().map(|row| row.iter().filter(|&&c| c).count()).collect::<Vec<_>>(), vec![1, 2, 0]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! readgrid {
+    ($height:expr, $ans:ident) => (
+        readgrid!(&mut std::io::stdin().lock(); $height, $ans);
+    );
+    ($height:expr, $ans:ident, $closure:expr) => (
+        readgrid!(&mut std::io::stdin().lock(); $height, $ans, $closure);
+    );
+    ($input:expr; $height:expr, $ans:ident) => (
+        let mut $ans = $crate::shared::grid::Grid::read(&mut *$input, $height);
+    );
+    ($input:expr; $height:expr, $ans:ident, $closure:expr) => (
+        let mut $ans = $crate::shared::grid::Grid::read_with(&mut *$input, $height, $closure);
+    );
+}
+
+/// Prints the given expressions with their values to stderr, prefixed by the file and line.
+///
+/// Tables, `Grid`s, `RectangleGraph`s and `Point`s are laid out by `PrettyDebug`,
+/// everything else is printed with `{:?}`.
+/// Expands to nothing without debug assertions, e.g. with `--release` or `rustc -O` as judges compile,
+/// so submissions may keep their calls.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate olympic_lib;
+/// # fn main() {
+/// let (a, b) = (1, "two");
+/// let grid = vec![vec!['.', '*'], vec!['*', '.']];
+/// debug!(a, b, grid);
+/// # }
+/// ```
+/// Prints `[src/main.rs:5] a = 1, b = "two", grid =` followed by the rows `['.', '*']` and `['*', '.']`.
+#[macro_export]
+macro_rules! debug {
+    ($( $x:expr ),+ $(,)*) => (
+        #[cfg(debug_assertions)]
+        {
+            #[allow(unused_imports)]
+            use $crate::shared::debug::{FallbackDebug, PrettyDebug};
+            let entries = [$( (stringify!($x), (&$x).pretty_debug()) ),+];
+            eprintln!("[{}:{}] {}", file!(), line!(), $crate::shared::debug::join_entries(&entries));
+        }
+    );
 }
\ No newline at end of file
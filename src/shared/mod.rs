@@ -1,6 +1,26 @@
-mod geometry;
-mod graph;
+pub mod bigints;
+pub mod bitmask;
+pub mod combinatorics;
+pub mod compress;
+pub mod debug;
+pub mod fenwick;
+pub mod fft;
+pub mod fps;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod integers;
+pub mod io;
 #[macro_use]
-mod macros;
-mod slices_extensions;
-//mod string;
\ No newline at end of file
+pub mod macros;
+pub mod modint;
+pub mod modular;
+pub mod monotonic;
+pub mod ntt;
+pub mod permutation;
+pub mod prefix_sums;
+pub mod primes;
+pub mod ratio;
+pub mod sequences;
+pub mod slices_extensions;
+pub mod string;
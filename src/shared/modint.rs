@@ -0,0 +1,328 @@
+use std::cell::Cell;
+use std::fmt::{Debug, Display, Formatter, Error};
+use std::hash::{Hash, Hasher};
+use std::iter::{Product, Sum};
+use std::marker::PhantomData;
+use std::mem::swap;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Source of the modulus of a `ModInt`, any value in `1..2^32`.
+pub trait Modulus {
+    fn modulus() -> u32;
+}
+
+/// Modulus known at compile time.
+pub enum Const<const M: u32> {}
+
+impl<const M: u32> Modulus for Const<M> {
+    #[inline]
+    fn modulus() -> u32 {
+        M
+    }
+}
+
+thread_local! {
+    static DYNAMIC_MODULUS: Cell<u32> = const { Cell::new(998_244_353) };
+}
+
+/// Modulus set at runtime, one for the whole thread, `998244353` until changed.
+pub enum Dynamic {}
+
+impl Dynamic {
+    /// Changes the modulus, values made under the previous one become meaningless.
+    pub fn set(modulus: u32) {
+        assert!(modulus > 0, "Zero modulus");
+        DYNAMIC_MODULUS.with(|m| m.set(modulus));
+    }
+}
+
+impl Modulus for Dynamic {
+    #[inline]
+    fn modulus() -> u32 {
+        DYNAMIC_MODULUS.with(|m| m.get())
+    }
+}
+
+/// Residue modulo `M::modulus()`, always kept reduced.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate olympic_lib;
+/// # use olympic_lib::shared::modint::{Dynamic, DynModInt, ModInt998244353, ModInt1000000007};
+/// # fn main() {
+/// type Mint = ModInt1000000007;
+/// let input = &mut &b"-1 123456789012345678901234567890\n"[..];
+/// readln!(input; a: Mint, b: Mint);
+/// assert_eq!(a.value(), 1_000_000_006);
+/// assert_eq!(b, Mint::from(123456789012345678901234567890_u128));
+/// assert_eq!(Mint::from(2).pow(1_000_000_006), Mint::from(1));
+/// assert_eq!(Mint::from(3) / Mint::from(6) * Mint::from(2), Mint::from(1));
+/// assert_eq!((Mint::from(5) - Mint::from(7)).to_string(), "1000000005");
+///
+/// let factorial: ModInt998244353 = (1..=10_u32).map(ModInt998244353::from).product();
+/// assert_eq!(factorial.value(), 3_628_800);
+/// let sum: ModInt998244353 = vec![ModInt998244353::from(-1); 3].iter().sum();
+/// assert_eq!(sum, -ModInt998244353::from(3));
+///
+/// Dynamic::set(10);
+/// assert_eq!((DynModInt::from(7) * DynModInt::from(8)).value(), 6);
+/// assert_eq!(DynModInt::from(3).inv(), DynModInt::from(7));
+/// assert_eq!(DynModInt::from(4).checked_inv(), None);
+/// # }
+/// ```
+pub struct ModInt<M> {
+    value: u32,
+    modulus: PhantomData<M>,
+}
+
+pub type ModInt998244353 = ModInt<Const<998_244_353>>;
+pub type ModInt1000000007 = ModInt<Const<1_000_000_007>>;
+pub type DynModInt = ModInt<Dynamic>;
+
+/// An error when parsing a `ModInt` from something other than an optional sign and digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseModIntError;
+
+impl Display for ParseModIntError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("invalid digit found in string")
+    }
+}
+
+impl<M: Modulus> ModInt<M> {
+    #[inline]
+    pub fn modulus() -> u32 {
+        M::modulus()
+    }
+
+    /// Wraps a value which is already reduced.
+    #[inline]
+    pub fn raw(value: u32) -> ModInt<M> {
+        debug_assert!(value < M::modulus(), "Value is not reduced");
+        ModInt{value, modulus: PhantomData}
+    }
+
+    #[inline]
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    pub fn pow(&self, mut exp: u64) -> ModInt<M> {
+        let mut base = *self;
+        let mut ans = ModInt::raw(1 % M::modulus());
+        while exp > 0 {
+            if exp & 1 == 1 {
+                ans *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        ans
+    }
+
+    /// Inverse by the extended Euclidean algorithm, so the modulus may be composite.
+    pub fn checked_inv(&self) -> Option<ModInt<M>> {
+        let (mut a, mut b) = (self.value as i64, M::modulus() as i64);
+        let (mut x, mut y) = (1_i64, 0_i64);
+        while b != 0 {
+            let q = a / b;
+            a -= q * b;
+            x -= q * y;
+            swap(&mut a, &mut b);
+            swap(&mut x, &mut y);
+        }
+        if a == 1 {
+            Some(ModInt::from(x))
+        } else {
+            None
+        }
+    }
+
+    /// Panics if the value is not coprime with the modulus.
+    #[inline]
+    pub fn inv(&self) -> ModInt<M> {
+        self.checked_inv().expect("Value is not invertible")
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => ($(
+        impl<M: Modulus> From<$t> for ModInt<M> {
+            #[inline]
+            fn from(value: $t) -> ModInt<M> {
+                ModInt::raw((value as u128 % M::modulus() as u128) as u32)
+            }
+        }
+    )*);
+}
+
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => ($(
+        impl<M: Modulus> From<$t> for ModInt<M> {
+            #[inline]
+            fn from(value: $t) -> ModInt<M> {
+                ModInt::raw((value as i128).rem_euclid(M::modulus() as i128) as u32)
+            }
+        }
+    )*);
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Reads any number of digits, reducing on the way.
+impl<M: Modulus> FromStr for ModInt<M> {
+    type Err = ParseModIntError;
+    fn from_str(s: &str) -> Result<ModInt<M>, ParseModIntError> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(&b'-') => (true, &s[1..]),
+            Some(&b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(ParseModIntError);
+        }
+        let modulus = M::modulus() as u64;
+        let value = digits.bytes().fold(0, |acc, c| (acc * 10 + (c - b'0') as u64) % modulus);
+        let ans = ModInt::raw(value as u32);
+        Ok(if negative { -ans } else { ans })
+    }
+}
+
+impl<M> Display for ModInt<M> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        Display::fmt(&self.value, f)
+    }
+}
+
+impl<M> Debug for ModInt<M> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        Debug::fmt(&self.value, f)
+    }
+}
+
+// Derives would require the bounds from the modulus marker as well
+impl<M> Clone for ModInt<M> {
+    #[inline]
+    fn clone(&self) -> ModInt<M> {
+        *self
+    }
+}
+
+impl<M> Copy for ModInt<M> {}
+
+impl<M> Default for ModInt<M> {
+    #[inline]
+    fn default() -> ModInt<M> {
+        ModInt{value: 0, modulus: PhantomData}
+    }
+}
+
+impl<M> PartialEq for ModInt<M> {
+    #[inline]
+    fn eq(&self, other: &ModInt<M>) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<M> Eq for ModInt<M> {}
+
+impl<M> Hash for ModInt<M> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<M: Modulus> Neg for ModInt<M> {
+    type Output = ModInt<M>;
+    #[inline]
+    fn neg(self) -> ModInt<M> {
+        if self.value == 0 { self } else { ModInt::raw(M::modulus() - self.value) }
+    }
+}
+
+impl<M: Modulus> Add for ModInt<M> {
+    type Output = ModInt<M>;
+    #[inline]
+    fn add(self, other: ModInt<M>) -> ModInt<M> {
+        let sum = self.value as u64 + other.value as u64;
+        let modulus = M::modulus() as u64;
+        ModInt::raw(if sum >= modulus { sum - modulus } else { sum } as u32)
+    }
+}
+
+impl<M: Modulus> Sub for ModInt<M> {
+    type Output = ModInt<M>;
+    #[inline]
+    fn sub(self, other: ModInt<M>) -> ModInt<M> {
+        if self.value >= other.value {
+            ModInt::raw(self.value - other.value)
+        } else {
+            ModInt::raw((self.value as u64 + M::modulus() as u64 - other.value as u64) as u32)
+        }
+    }
+}
+
+impl<M: Modulus> Mul for ModInt<M> {
+    type Output = ModInt<M>;
+    #[inline]
+    fn mul(self, other: ModInt<M>) -> ModInt<M> {
+        ModInt::raw((self.value as u64 * other.value as u64 % M::modulus() as u64) as u32)
+    }
+}
+
+impl<M: Modulus> Div for ModInt<M> {
+    type Output = ModInt<M>;
+    #[inline]
+    fn div(self, other: ModInt<M>) -> ModInt<M> {
+        self.mul(other.inv())
+    }
+}
+
+macro_rules! forward_assign {
+    ($($assign_imp:ident, $assign_method:ident, $method:ident);*) => ($(
+        impl<M: Modulus> $assign_imp for ModInt<M> {
+            #[inline]
+            fn $assign_method(&mut self, other: ModInt<M>) {
+                *self = self.$method(other);
+            }
+        }
+    )*);
+}
+
+forward_assign!(AddAssign, add_assign, add; SubAssign, sub_assign, sub;
+                MulAssign, mul_assign, mul; DivAssign, div_assign, div);
+
+impl<M: Modulus> Sum for ModInt<M> {
+    #[inline]
+    fn sum<I: Iterator<Item = ModInt<M>>>(iter: I) -> ModInt<M> {
+        iter.fold(ModInt::default(), Add::add)
+    }
+}
+
+impl<'a, M: Modulus> Sum<&'a ModInt<M>> for ModInt<M> {
+    #[inline]
+    fn sum<I: Iterator<Item = &'a ModInt<M>>>(iter: I) -> ModInt<M> {
+        iter.cloned().sum()
+    }
+}
+
+impl<M: Modulus> Product for ModInt<M> {
+    #[inline]
+    fn product<I: Iterator<Item = ModInt<M>>>(iter: I) -> ModInt<M> {
+        iter.fold(ModInt::from(1_u32), Mul::mul)
+    }
+}
+
+impl<'a, M: Modulus> Product<&'a ModInt<M>> for ModInt<M> {
+    #[inline]
+    fn product<I: Iterator<Item = &'a ModInt<M>>>(iter: I) -> ModInt<M> {
+        iter.cloned().product()
+    }
+}
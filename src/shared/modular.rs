@@ -0,0 +1,489 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::mem::swap;
+use shared::bigints::big_prod;
+use shared::integers::isqrt;
+use shared::primes::{factorize, is_prime};
+
+/// `a * b mod m` without overflow for any `m > 0`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::modular::{mulmod, powmod};
+/// let m = (1 << 61) - 1;
+/// assert_eq!(mulmod(m - 1, m - 1, m), 1);
+/// assert_eq!(mulmod(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+/// assert_eq!(powmod(3, m - 1, m), 1);
+/// assert_eq!(powmod(0, 0, 7), 1);
+/// assert_eq!(powmod(5, 3, 1), 0);
+/// ```
+#[inline]
+pub fn mulmod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// `base^exp mod m`, with `0^0 = 1`.
+pub fn powmod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut ans = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            ans = mulmod(ans, base, m);
+        }
+        base = mulmod(base, base, m);
+        exp >>= 1;
+    }
+    ans
+}
+
+/// Montgomery multiplication modulo a fixed odd `n`, trading the 128-bit division for multiplications.
+///
+/// Values in Montgomery form are `x * 2^64 mod n`; convert with `transform` and `restore`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::modular::{mulmod, powmod, Montgomery};
+/// let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+/// let mut next = || { seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17; seed };
+/// for &n in &[3, 1_000_000_007, (1 << 61) - 1, u64::MAX, u64::MAX - 58] {
+///     let mont = Montgomery::new(n);
+///     for _ in 0..100 {
+///         let (a, b, e) = (next() % n, next() % n, next());
+///         let (x, y) = (mont.transform(a), mont.transform(b));
+///         assert_eq!(mont.restore(mont.mul(x, y)), mulmod(a, b, n));
+///         assert_eq!(mont.restore(mont.add(x, y)), ((a as u128 + b as u128) % n as u128) as u64);
+///         assert_eq!(mont.restore(mont.pow(x, e)), powmod(a, e, n));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Montgomery {
+    n: u64,
+    // n * n_inv = 1 mod 2^64
+    n_inv: u64,
+    // 2^128 mod n
+    r2: u64,
+}
+
+impl Montgomery {
+    pub fn new(n: u64) -> Montgomery {
+        assert!(n & 1 == 1, "Even modulus");
+        // Every Newton step doubles the number of correct low bits, starting from 3
+        let mut n_inv = n;
+        for _ in 0..5 {
+            n_inv = n_inv.wrapping_mul(2_u64.wrapping_sub(n.wrapping_mul(n_inv)));
+        }
+        let r2 = ((u128::MAX % n as u128 + 1) % n as u128) as u64;
+        Montgomery{n, n_inv, r2}
+    }
+
+    #[inline]
+    pub fn modulus(&self) -> u64 {
+        self.n
+    }
+
+    /// `high * 2^64 + low` divided by `2^64` modulo `n`, for values below `n * 2^64`.
+    #[inline]
+    fn reduce(&self, (high, low): (u64, u64)) -> u64 {
+        let m = low.wrapping_mul(self.n_inv);
+        // The low halves of t and mn are equal, so only the high ones are subtracted
+        let (ans, borrow) = high.overflowing_sub(big_prod(m, self.n).0);
+        if borrow { ans.wrapping_add(self.n) } else { ans }
+    }
+
+    /// Montgomery form of `x`.
+    #[inline]
+    pub fn transform(&self, x: u64) -> u64 {
+        self.reduce(big_prod(x % self.n, self.r2))
+    }
+
+    /// Ordinary value of `x` in Montgomery form.
+    #[inline]
+    pub fn restore(&self, x: u64) -> u64 {
+        self.reduce((0, x))
+    }
+
+    #[inline]
+    pub fn one(&self) -> u64 {
+        self.transform(1)
+    }
+
+    #[inline]
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(big_prod(a, b))
+    }
+
+    #[inline]
+    pub fn add(&self, a: u64, b: u64) -> u64 {
+        let (sum, carry) = a.overflowing_add(b);
+        if carry || sum >= self.n { sum.wrapping_sub(self.n) } else { sum }
+    }
+
+    #[inline]
+    pub fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b { a - b } else { a.wrapping_sub(b).wrapping_add(self.n) }
+    }
+
+    pub fn pow(&self, mut base: u64, mut exp: u64) -> u64 {
+        let mut ans = self.one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                ans = self.mul(ans, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        ans
+    }
+}
+
+/// Barrett reduction modulo a fixed `m` below `2^32`, e.g. a modulus read at runtime.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::modular::Barrett;
+/// let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+/// let mut next = || { seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17; seed };
+/// for &m in &[1, 2, 998_244_353, u32::MAX - 4, u32::MAX] {
+///     let barrett = Barrett::new(m);
+///     for _ in 0..100 {
+///         let (a, b, z) = ((next() % m as u64) as u32, (next() % m as u64) as u32, next());
+///         assert_eq!(barrett.reduce(z) as u64, z % m as u64);
+///         assert_eq!(barrett.mul(a, b) as u64, a as u64 * b as u64 % m as u64);
+///     }
+/// }
+/// assert_eq!(Barrett::new(1_000_000_007).pow(2, 1_000_000_006), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Barrett {
+    m: u32,
+    // ceil(2^64 / m), or 0 for m = 1
+    m_inv: u64,
+}
+
+impl Barrett {
+    pub fn new(m: u32) -> Barrett {
+        assert!(m > 0, "Zero modulus");
+        Barrett{m, m_inv: (u64::MAX / m as u64).wrapping_add(1)}
+    }
+
+    #[inline]
+    pub fn modulus(&self) -> u32 {
+        self.m
+    }
+
+    /// `z mod m` for any `u64`.
+    #[inline]
+    pub fn reduce(&self, z: u64) -> u32 {
+        if self.m == 1 {
+            return 0;
+        }
+        // The estimated quotient is exact or one too large
+        let quotient = big_prod(z, self.m_inv).0;
+        let (ans, borrow) = z.overflowing_sub(quotient.wrapping_mul(self.m as u64));
+        if borrow { ans.wrapping_add(self.m as u64) as u32 } else { ans as u32 }
+    }
+
+    #[inline]
+    pub fn mul(&self, a: u32, b: u32) -> u32 {
+        self.reduce(a as u64 * b as u64)
+    }
+
+    pub fn pow(&self, base: u32, mut exp: u64) -> u32 {
+        let mut base = self.reduce(base as u64);
+        let mut ans = self.reduce(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                ans = self.mul(ans, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        ans
+    }
+}
+
+/// Integers the number theory functions below accept, all computed through `i128` and `u128`.
+pub trait ModularInteger: Copy {
+    fn to_i128(self) -> i128;
+    /// Panics if the value does not fit.
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_modular_integer {
+    ($($t:ty),*) => ($(
+        impl ModularInteger for $t {
+            #[inline]
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            #[inline]
+            fn from_i128(value: i128) -> $t {
+                <$t>::try_from(value).expect("Value does not fit")
+            }
+        }
+    )*);
+}
+
+impl_modular_integer!(u32, u64, usize, i32, i64, isize);
+
+/// A positive modulus as `u64`.
+#[inline]
+fn modulus_u64<T: ModularInteger>(m: T) -> u64 {
+    let m = m.to_i128();
+    assert!(m > 0, "Non-positive modulus");
+    m as u64
+}
+
+/// `a mod m` in `0..m`, also for negative `a`.
+#[inline]
+fn residue<T: ModularInteger>(a: T, m: u64) -> u64 {
+    a.to_i128().rem_euclid(m as i128) as u64
+}
+
+fn ext_gcd_i128(mut a: i128, mut b: i128) -> (i128, i128, i128) {
+    let (mut x, mut next_x, mut y, mut next_y) = (1, 0, 0, 1);
+    while b != 0 {
+        let q = a / b;
+        a -= q * b;
+        x -= q * next_x;
+        y -= q * next_y;
+        swap(&mut a, &mut b);
+        swap(&mut x, &mut next_x);
+        swap(&mut y, &mut next_y);
+    }
+    if a < 0 { (-a, -x, -y) } else { (a, x, y) }
+}
+
+/// `(g, x, y)` with `g = gcd(a, b) >= 0` and `a x + b y = g`.
+///
+/// The coefficients are `i128`, as for `u64` they may not fit into the type itself.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::modular::{ext_gcd, mod_inv};
+/// assert_eq!(ext_gcd(240_u64, 46), (2, -9, 47));
+/// assert_eq!(ext_gcd(-12_i64, 18), (6, 1, 1));
+/// assert_eq!(ext_gcd(0_i64, 0), (0, 1, 0));
+/// let (g, x, y) = ext_gcd(u64::MAX, u64::MAX - 1);
+/// assert_eq!((g, x * u64::MAX as i128 + y * (u64::MAX - 1) as i128), (1, 1));
+///
+/// assert_eq!(mod_inv(3_u64, 10), Some(7));
+/// assert_eq!(mod_inv(-3_i64, 10), Some(3));
+/// assert_eq!(mod_inv(4_u64, 10), None);
+/// assert_eq!(mod_inv(5_u64, 1), Some(0));
+/// assert_eq!(mod_inv(u64::MAX - 1, u64::MAX), Some(u64::MAX - 1));
+/// ```
+pub fn ext_gcd<T: ModularInteger>(a: T, b: T) -> (T, i128, i128) {
+    let (g, x, y) = ext_gcd_i128(a.to_i128(), b.to_i128());
+    (T::from_i128(g), x, y)
+}
+
+/// Inverse of `a` modulo any positive `m`, in `0..m`, or `None` if they are not coprime.
+pub fn mod_inv<T: ModularInteger>(a: T, m: T) -> Option<T> {
+    let m = modulus_u64(m);
+    let (g, x, _) = ext_gcd_i128(residue(a, m) as i128, m as i128);
+    if g == 1 {
+        Some(T::from_i128(x.rem_euclid(m as i128)))
+    } else {
+        None
+    }
+}
+
+/// Solves `x = r mod m` for all pairs `(r, m)` with any positive moduli, returning `(x, lcm)` with `0 <= x < lcm`.
+///
+/// `None` if the congruences contradict each other; the least common multiple must fit into `T`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::modular::crt;
+/// assert_eq!(crt(&[(2_u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(2_u64, 6), (5, 9)]), Some((14, 18)));
+/// assert_eq!(crt(&[(1_u64, 4), (2, 6)]), None);
+/// assert_eq!(crt(&[(-1_i64, 5), (-2, 7)]), Some((19, 35)));
+/// assert_eq!(crt::<u64>(&[]), Some((0, 1)));
+///
+/// let (p, q) = (4_294_967_291_u64, 4_294_967_279_u64);
+/// let (x, lcm) = crt(&[(1, p), (q - 1, q)]).unwrap();
+/// assert_eq!((x % p, x % q, lcm), (1, q - 1, p * q));
+/// let (a, b) = (2_147_483_647_i64, 2_147_483_629_i64);
+/// let (x, lcm) = crt(&[(a - 1, a * 2), (-6, b * 2)]).unwrap();
+/// assert_eq!((x % (a * 2), x % (b * 2), lcm), (a - 1, b * 2 - 6, a * b * 2));
+/// ```
+pub fn crt<T: ModularInteger>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let (mut r, mut m) = (0_u64, 1_u64);
+    for &(r_next, m_next) in congruences {
+        let m_next = modulus_u64(m_next);
+        let r_next = residue(r_next, m_next);
+        // p m = g mod m_next, so r + m p (r_next - r) / g fits both
+        let (g, p, _) = ext_gcd_i128(m as i128, m_next as i128);
+        let diff = r_next as i128 - r as i128;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = (m_next as i128 / g) as u64;
+        let k = mulmod((diff / g).rem_euclid(step as i128) as u64, p.rem_euclid(step as i128) as u64, step);
+        r = (r as u128 + m as u128 * k as u128) as u64;
+        m = m.checked_mul(step).expect("The least common multiple overflows");
+    }
+    Some((T::from_i128(r as i128), T::from_i128(m as i128)))
+}
+
+/// Smallest `k >= 0` with `base^k = target mod m` for any positive `m`, by baby-step giant-step in `O(sqrt m)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::modular::{discrete_log, powmod};
+/// let p = 1_000_000_007_u64;
+/// let k = discrete_log(5, 123_456_789, p).unwrap();
+/// assert_eq!(powmod(5, k, p), 123_456_789);
+/// assert_eq!(discrete_log(2_u64, 0, 8), Some(3));
+/// assert_eq!(discrete_log(2_u64, 3, 8), None);
+/// assert_eq!(discrete_log(-2_i64, 4, 5), Some(2));
+///
+/// for m in 1..40_u64 {
+///     for a in 0..m {
+///         for b in 0..m {
+///             let naive = (0..2 * m).find(|&k| powmod(a, k, m) == b);
+///             assert_eq!(discrete_log(a, b, m), naive);
+///         }
+///     }
+/// }
+/// ```
+pub fn discrete_log<T: ModularInteger>(base: T, target: T, m: T) -> Option<T> {
+    let mut m = modulus_u64(m);
+    let (base, mut target) = (residue(base, m), residue(target, m));
+    // Common factors of the base and the modulus are divided out first, each one costs one step
+    let (mut scale, mut steps) = (1 % m, 0_u64);
+    loop {
+        let g = ext_gcd_i128(base as i128, m as i128).0 as u64;
+        if g == 1 {
+            break;
+        }
+        if target == scale {
+            return Some(T::from_i128(steps as i128));
+        }
+        if target % g != 0 {
+            return None;
+        }
+        target /= g;
+        m /= g;
+        steps += 1;
+        scale = mulmod(scale, base / g, m);
+    }
+    // Now solve scale * base^k = target with base coprime to m, as k = n p - q
+    // n = ceil(sqrt(m)), computed without squaring as n * n overflows for m near u64::MAX
+    let n = isqrt(m - 1) + 1;
+    let mut baby = HashMap::new();
+    let mut current = target % m;
+    for q in 0..=n {
+        baby.insert(current, q);
+        current = mulmod(current, base, m);
+    }
+    let giant = powmod(base, n, m);
+    let mut current = scale % m;
+    for p in 1..=n {
+        current = mulmod(current, giant, m);
+        if let Some(&q) = baby.get(&current) {
+            return Some(T::from_i128(n as i128 * p as i128 - q as i128 + steps as i128));
+        }
+    }
+    None
+}
+
+/// Smallest primitive root modulo `m`, or `None` if `m` is not `1`, `2`, `4`, `p^k` or `2 p^k` for an odd prime `p`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::modular::primitive_root;
+/// assert_eq!(primitive_root(998_244_353_u64), Some(3));
+/// assert_eq!(primitive_root(1_000_000_007_u64), Some(5));
+/// assert_eq!(primitive_root((1_u64 << 61) - 1), Some(37));
+/// assert_eq!(primitive_root(2 * 3_u64.pow(30)), Some(5));
+/// assert_eq!(primitive_root(1_i64), Some(0));
+/// assert_eq!((primitive_root(2_u64), primitive_root(4_u64)), (Some(1), Some(3)));
+/// assert_eq!((primitive_root(8_u64), primitive_root(15_u64)), (None, None));
+/// ```
+pub fn primitive_root<T: ModularInteger>(m: T) -> Option<T> {
+    let m = modulus_u64(m);
+    if m <= 4 {
+        return [None, Some(0), Some(1), Some(2), Some(3)][m as usize].map(T::from_i128);
+    }
+    let odd = if m % 4 == 2 { m / 2 } else { m };
+    let factors = factorize(odd);
+    if odd % 2 == 0 || factors.len() != 1 {
+        return None;
+    }
+    let (p, exp) = factors[0];
+    let phi = p.pow(exp - 1) * (p - 1);
+    let phi_factors = factorize(phi);
+    (2..m).find(|&g| {
+        ext_gcd_i128(g as i128, m as i128).0 == 1
+            && phi_factors.iter().all(|&(q, _)| powmod(g, phi / q, m) != 1)
+    }).map(|g| T::from_i128(g as i128))
+}
+
+/// Smaller square root of `a` modulo a prime `p` by Tonelli–Shanks, or `None` if `a` is not a square.
+///
+/// Panics if `p` is not prime.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::modular::{mulmod, sqrt_mod};
+/// assert_eq!(sqrt_mod(2_u64, 7), Some(3));
+/// assert_eq!(sqrt_mod(3_u64, 7), None);
+/// assert_eq!(sqrt_mod(-3_i64, 7), Some(2));
+/// assert_eq!((sqrt_mod(0_u64, 13), sqrt_mod(1_u32, 2)), (Some(0), Some(1)));
+/// for &p in &[998_244_353_u64, 1_000_000_007, (1 << 61) - 1, u64::MAX - 58] {
+///     for x in 1..100 {
+///         let x = mulmod(x, 0x9E37_79B9_7F4A_7C15, p);
+///         assert_eq!(sqrt_mod(mulmod(x, x, p), p), Some(x.min(p - x)));
+///     }
+/// }
+/// ```
+///
+/// ```rust,should_panic
+/// # use olympic_lib::shared::modular::sqrt_mod;
+/// // 4 is a square modulo 15, but 15 is not prime
+/// sqrt_mod(4_u64, 15);
+/// ```
+pub fn sqrt_mod<T: ModularInteger>(a: T, p: T) -> Option<T> {
+    let p = modulus_u64(p);
+    assert!(is_prime(p), "Modulus is not prime");
+    let a = residue(a, p);
+    if a == 0 || p == 2 {
+        return Some(T::from_i128(a as i128));
+    }
+    if powmod(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    let shift = (p - 1).trailing_zeros();
+    let odd = (p - 1) >> shift;
+    let non_residue = (2..p).find(|&z| powmod(z, (p - 1) / 2, p) != 1).unwrap();
+    // Invariant: ans^2 = a t, where t has order 2^i with i < m, and z has order 2^m
+    let (mut m, mut z) = (shift, powmod(non_residue, odd, p));
+    let (mut t, mut ans) = (powmod(a, odd, p), powmod(a, (odd + 1) / 2, p));
+    while t != 1 {
+        let mut i = 0;
+        let mut square = t;
+        while square != 1 {
+            square = mulmod(square, square, p);
+            i += 1;
+        }
+        let b = powmod(z, 1 << (m - i - 1), p);
+        m = i;
+        z = mulmod(b, b, p);
+        t = mulmod(t, z, p);
+        ans = mulmod(ans, b, p);
+    }
+    Some(T::from_i128(ans.min(p - ans) as i128))
+}
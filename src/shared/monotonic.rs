@@ -0,0 +1,285 @@
+use std::collections::VecDeque;
+
+/// Window of the latest pushed items, keeping only those which can still become the best one.
+///
+/// Items are numbered in the order they are pushed. Every operation is amortized `O(1)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::monotonic::MonotonicDeque;
+/// let mut d = MonotonicDeque::min();
+/// for &v in &[4, 2, 5, 3] {
+///     d.push(v);
+/// }
+/// assert_eq!((d.best(), d.best_index()), (Some(&2), Some(1)));
+/// d.expire(2);
+/// assert_eq!(d.best(), Some(&3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MonotonicDeque<T> {
+    deque: VecDeque<(usize, T)>,
+    pushed: usize,
+    better: fn(&T, &T) -> bool,
+}
+
+impl<T: PartialOrd> MonotonicDeque<T> {
+    /// Keeps track of the minimum, the earliest one among equal items.
+    #[inline]
+    pub fn min() -> MonotonicDeque<T> {
+        MonotonicDeque::new(|a, b| a < b)
+    }
+
+    /// Keeps track of the maximum, the earliest one among equal items.
+    #[inline]
+    pub fn max() -> MonotonicDeque<T> {
+        MonotonicDeque::new(|a, b| a > b)
+    }
+}
+
+impl<T> MonotonicDeque<T> {
+    /// `better(a, b)` tells whether a later `a` makes an earlier `b` useless.
+    #[inline]
+    pub fn new(better: fn(&T, &T) -> bool) -> MonotonicDeque<T> {
+        MonotonicDeque{deque: VecDeque::new(), pushed: 0, better}
+    }
+
+    /// Pushes `value` and returns its index.
+    pub fn push(&mut self, value: T) -> usize {
+        while self.deque.back().is_some_and(|back| (self.better)(&value, &back.1)) {
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.pushed, value));
+        self.pushed += 1;
+        self.pushed - 1
+    }
+
+    /// Drops the items with indices less than `start`.
+    pub fn expire(&mut self, start: usize) {
+        while self.deque.front().is_some_and(|front| front.0 < start) {
+            self.deque.pop_front();
+        }
+    }
+
+    #[inline]
+    pub fn best(&self) -> Option<&T> {
+        self.deque.front().map(|front| &front.1)
+    }
+
+    #[inline]
+    pub fn best_index(&self) -> Option<usize> {
+        self.deque.front().map(|front| front.0)
+    }
+}
+
+/// Indices of the minima of all windows `x[i..i + k]`, the leftmost one among equal items.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::monotonic::{sliding_max, sliding_min};
+/// let x = [1, 3, -1, -3, 5, 3, 6, 7];
+/// assert_eq!(sliding_min(&x, 3), vec![2, 3, 3, 3, 5, 5]);
+/// assert_eq!(sliding_max(&x, 3).iter().map(|&i| x[i]).collect::<Vec<_>>(), vec![3, 3, 5, 5, 6, 7]);
+/// assert!(sliding_min(&x, 9).is_empty());
+/// ```
+#[inline]
+pub fn sliding_min<T: PartialOrd>(x: &[T], k: usize) -> Vec<usize> {
+    sliding_best(x, k, |a, b| a < b)
+}
+
+/// Indices of the maxima of all windows `x[i..i + k]`, see `sliding_min`.
+#[inline]
+pub fn sliding_max<T: PartialOrd>(x: &[T], k: usize) -> Vec<usize> {
+    sliding_best(x, k, |a, b| a > b)
+}
+
+fn sliding_best<T>(x: &[T], k: usize, better: fn(&&T, &&T) -> bool) -> Vec<usize> {
+    assert!(k > 0, "Empty window");
+    let mut deque = MonotonicDeque::new(better);
+    let mut ans = Vec::with_capacity((x.len() + 1).saturating_sub(k));
+    for (i, value) in x.iter().enumerate() {
+        deque.push(value);
+        if i + 1 >= k {
+            deque.expire(i + 1 - k);
+            ans.push(deque.best_index().unwrap());
+        }
+    }
+    ans
+}
+
+/// Queue keeping the aggregate of its items under an associative `op`, e.g. min, gcd or a sum mod p.
+///
+/// Made of two stacks, so every operation is amortized `O(1)` calls of `op`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::monotonic::AggregateQueue;
+/// let mut q = AggregateQueue::new(|a: &u64, b: &u64| *a.max(b));
+/// q.push_back(3);
+/// q.push_back(8);
+/// q.push_back(5);
+/// assert_eq!(q.aggregate(), Some(8));
+/// assert_eq!((q.pop_front(), q.pop_front()), (Some(3), Some(8)));
+/// assert_eq!((q.aggregate(), q.len()), (Some(5), 1));
+///
+/// let mut q = AggregateQueue::new(|a: &String, b: &String| a.clone() + b);
+/// q.push_back("ab".to_string());
+/// q.push_back("c".to_string());
+/// q.pop_front();
+/// q.push_back("d".to_string());
+/// assert_eq!(q.aggregate(), Some("cd".to_string()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AggregateQueue<T, F> {
+    // Items with the aggregates from them to the end of the queue part, front is on top
+    front: Vec<(T, T)>,
+    // Items with the aggregates from the start of the queue part to them, back is on top
+    back: Vec<(T, T)>,
+    op: F,
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> AggregateQueue<T, F> {
+    #[inline]
+    pub fn new(op: F) -> AggregateQueue<T, F> {
+        AggregateQueue{front: Vec::new(), back: Vec::new(), op}
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let total = match self.back.last() {
+            Some(top) => (self.op)(&top.1, &value),
+            None => value.clone(),
+        };
+        self.back.push((value, total));
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            while let Some((value, _)) = self.back.pop() {
+                let total = match self.front.last() {
+                    Some(top) => (self.op)(&value, &top.1),
+                    None => value.clone(),
+                };
+                self.front.push((value, total));
+            }
+        }
+        self.front.pop().map(|(value, _)| value)
+    }
+
+    /// `op` folded over all items from front to back.
+    pub fn aggregate(&self) -> Option<T> {
+        match (self.front.last(), self.back.last()) {
+            (Some(f), Some(b)) => Some((self.op)(&f.1, &b.1)),
+            (Some(f), None) => Some(f.1.clone()),
+            (None, Some(b)) => Some(b.1.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// For every `i`, the largest `j < i` with `x[j] < x[i]`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::monotonic::{next_greater, next_smaller, previous_greater, previous_smaller};
+/// let x = [2, 1, 4, 3, 4];
+/// assert_eq!(previous_smaller(&x), vec![None, None, Some(1), Some(1), Some(3)]);
+/// assert_eq!(next_smaller(&x), vec![Some(1), None, Some(3), None, None]);
+/// assert_eq!(previous_greater(&x), vec![None, Some(0), None, Some(2), None]);
+/// assert_eq!(next_greater(&x), vec![Some(2), Some(2), None, Some(4), None]);
+/// ```
+#[inline]
+pub fn previous_smaller<T: PartialOrd>(x: &[T]) -> Vec<Option<usize>> {
+    nearest(x, 0..x.len(), |a, b| a < b)
+}
+
+/// For every `i`, the smallest `j > i` with `x[j] < x[i]`.
+#[inline]
+pub fn next_smaller<T: PartialOrd>(x: &[T]) -> Vec<Option<usize>> {
+    nearest(x, (0..x.len()).rev(), |a, b| a < b)
+}
+
+/// For every `i`, the largest `j < i` with `x[j] > x[i]`.
+#[inline]
+pub fn previous_greater<T: PartialOrd>(x: &[T]) -> Vec<Option<usize>> {
+    nearest(x, 0..x.len(), |a, b| a > b)
+}
+
+/// For every `i`, the smallest `j > i` with `x[j] > x[i]`.
+#[inline]
+pub fn next_greater<T: PartialOrd>(x: &[T]) -> Vec<Option<usize>> {
+    nearest(x, (0..x.len()).rev(), |a, b| a > b)
+}
+
+/// Monotonic stack walked in the `order` of indices, `fits(x[j], x[i])` tells whether `j` is an answer for `i`.
+fn nearest<T, I, F>(x: &[T], order: I, fits: F) -> Vec<Option<usize>>
+    where I: Iterator<Item = usize>, F: Fn(&T, &T) -> bool {
+    let mut ans = vec![None; x.len()];
+    let mut stack: Vec<usize> = Vec::new();
+    for i in order {
+        while stack.last().is_some_and(|&j| !fits(&x[j], &x[i])) {
+            stack.pop();
+        }
+        ans[i] = stack.last().cloned();
+        stack.push(i);
+    }
+    ans
+}
+
+/// Binary tree on indices which is a min-heap by value and has `x` as its in-order traversal.
+///
+/// Among equal values the leftmost one is the ancestor.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::monotonic::CartesianTree;
+/// let t = CartesianTree::new(&[3, 1, 4, 1, 5]);
+/// assert_eq!(t.root, Some(1));
+/// assert_eq!((t.left[1], t.right[1]), (Some(0), Some(3)));
+/// assert_eq!((t.left[3], t.right[3]), (Some(2), Some(4)));
+/// assert_eq!(t.parent, vec![Some(1), None, Some(3), Some(1), Some(3)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CartesianTree {
+    pub root: Option<usize>,
+    pub parent: Vec<Option<usize>>,
+    pub left: Vec<Option<usize>>,
+    pub right: Vec<Option<usize>>,
+}
+
+impl CartesianTree {
+    pub fn new<T: PartialOrd>(x: &[T]) -> CartesianTree {
+        let n = x.len();
+        let (mut parent, mut left, mut right) = (vec![None; n], vec![None; n], vec![None; n]);
+        let mut stack: Vec<usize> = Vec::new();
+        for i in 0..n {
+            let mut last = None;
+            while stack.last().is_some_and(|&j| x[i] < x[j]) {
+                last = stack.pop();
+            }
+            if let Some(child) = last {
+                parent[child] = Some(i);
+                left[i] = Some(child);
+            }
+            if let Some(&top) = stack.last() {
+                parent[i] = Some(top);
+                right[top] = Some(i);
+            }
+            stack.push(i);
+        }
+        CartesianTree{root: stack.first().cloned(), parent, left, right}
+    }
+}
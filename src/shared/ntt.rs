@@ -0,0 +1,146 @@
+use shared::modular::powmod;
+
+/// Primes `c * 2^k + 1` with primitive root `3`, supporting transforms of length up to `2^23`.
+pub const NTT_PRIMES: [u64; 3] = [998_244_353, 167_772_161, 469_762_049];
+
+/// In-place number-theoretic transform modulo an NTT prime with primitive root `3`.
+///
+/// The length must be a power of two dividing `modulus - 1`; the inverse transform is normalized.
+pub fn ntt(a: &mut [u64], invert: bool, modulus: u64) {
+    let n = a.len();
+    assert!(n.is_power_of_two() && (modulus - 1) % n as u64 == 0, "Unsupported NTT length");
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let mut root = powmod(3, (modulus - 1) / len as u64, modulus);
+        if invert {
+            root = powmod(root, modulus - 2, modulus);
+        }
+        let half = len / 2;
+        let mut powers = Vec::with_capacity(half);
+        powers.push(1);
+        for k in 1..half {
+            powers.push(powers[k - 1] * root % modulus);
+        }
+        for chunk in a.chunks_mut(len) {
+            let (left, right) = chunk.split_at_mut(half);
+            for k in 0..half {
+                let u = left[k];
+                let v = right[k] * powers[k] % modulus;
+                left[k] = if u + v >= modulus { u + v - modulus } else { u + v };
+                right[k] = if u >= v { u - v } else { u + modulus - v };
+            }
+        }
+        len <<= 1;
+    }
+    if invert {
+        let n_inv = powmod(n as u64, modulus - 2, modulus);
+        for x in a.iter_mut() {
+            *x = *x * n_inv % modulus;
+        }
+    }
+}
+
+/// Convolution modulo an NTT prime; inputs must be reduced.
+///
+/// Unless the shorter input is tiny, `a.len() + b.len() - 1` rounded up to a power of two must divide
+/// `modulus - 1`, i.e. be at most `2^23` for `NTT_PRIMES`; `ntt` panics otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::ntt::convolution_mod;
+/// assert_eq!(convolution_mod(&[1, 2, 3], &[4, 5], 998_244_353), vec![4, 13, 22, 15]);
+/// assert_eq!(convolution_mod(&[998_244_352], &[998_244_352], 998_244_353), vec![1]);
+/// ```
+pub fn convolution_mod(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+    if a.len().min(b.len()) <= 32 {
+        let mut ans = vec![0; len];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                ans[i + j] = (ans[i + j] + x * y) % modulus;
+            }
+        }
+        return ans;
+    }
+    let size = len.next_power_of_two();
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(size, 0);
+    fb.resize(size, 0);
+    ntt(&mut fa, false, modulus);
+    ntt(&mut fb, false, modulus);
+    for (x, &y) in fa.iter_mut().zip(&fb) {
+        *x = *x * y % modulus;
+    }
+    ntt(&mut fa, true, modulus);
+    fa.truncate(len);
+    fa
+}
+
+/// Exact convolution of non-negative integers, by three NTT primes and the Chinese remainder theorem.
+///
+/// Every coefficient of the result must be below `NTT_PRIMES` multiplied, about `7.8e25`,
+/// e.g. inputs below `2^32` with the shorter one under `2^22` items.
+/// The result length `a.len() + b.len() - 1` must be at most `2^23`, the longest transform of `NTT_PRIMES`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::ntt::convolution_exact;
+/// let a = vec![u32::MAX as u64; 100];
+/// let ans = convolution_exact(&a, &a);
+/// assert_eq!(ans[99], 100 * (u32::MAX as u128).pow(2));
+/// assert_eq!(ans[0], (u32::MAX as u128).pow(2));
+/// ```
+pub fn convolution_exact(a: &[u64], b: &[u64]) -> Vec<u128> {
+    let [m1, m2, m3] = NTT_PRIMES;
+    let reduce = |x: &[u64], m: u64| x.iter().map(|&v| v % m).collect::<Vec<u64>>();
+    let r1 = convolution_mod(&reduce(a, m1), &reduce(b, m1), m1);
+    let r2 = convolution_mod(&reduce(a, m2), &reduce(b, m2), m2);
+    let r3 = convolution_mod(&reduce(a, m3), &reduce(b, m3), m3);
+    let m1_inv = powmod(m1, m2 - 2, m2);
+    let m12_inv = powmod(m1 * m2 % m3, m3 - 2, m3);
+    (0..r1.len()).map(|i| {
+        // Garner's algorithm: x = x1 + x2 * m1 + x3 * m1 * m2
+        let x1 = r1[i];
+        let x2 = (r2[i] + m2 - x1 % m2) % m2 * m1_inv % m2;
+        let partial = (x1 + x2 * m1) % m3;
+        let x3 = (r3[i] + m3 - partial) % m3 * m12_inv % m3;
+        x1 as u128 + x2 as u128 * m1 as u128 + x3 as u128 * (m1 * m2) as u128
+    }).collect()
+}
+
+/// Convolution modulo any `modulus` below `2^32`; inputs must be reduced and the shorter one under `2^22` items.
+///
+/// As for `convolution_exact`, `a.len() + b.len() - 1` must be at most `2^23`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::ntt::convolution_arbitrary;
+/// let m = 1_000_000_007;
+/// let a: Vec<u64> = (0..1000).map(|i| (i * i * 1_000_003) % m).collect();
+/// let b: Vec<u64> = (0..700).map(|i| m - 1 - i).collect();
+/// let ans = convolution_arbitrary(&a, &b, m);
+/// assert_eq!(ans[999], (300..1000).fold(0, |acc, i| (acc + a[i] * b[999 - i]) % m));
+/// assert_eq!(ans[0], a[0] * b[0] % m);
+/// ```
+pub fn convolution_arbitrary(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+    convolution_exact(a, b).into_iter().map(|x| (x % modulus as u128) as u64).collect()
+}
@@ -0,0 +1,151 @@
+//! Permutations of `0..n` stored as `p[i]`, the image of `i`.
+
+use shared::fenwick::Fenwick;
+
+/// Whether `p` contains every number of `0..p.len()` exactly once.
+pub fn is_permutation(p: &[usize]) -> bool {
+    let mut seen = vec![false; p.len()];
+    for &v in p {
+        if v >= p.len() || seen[v] {
+            return false;
+        }
+        seen[v] = true;
+    }
+    true
+}
+
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::permutation::{compose, inverse};
+/// let p = [2, 0, 3, 1];
+/// assert_eq!(inverse(&p), vec![1, 3, 0, 2]);
+/// assert_eq!(compose(&p, &inverse(&p)), vec![0, 1, 2, 3]);
+/// ```
+pub fn inverse(p: &[usize]) -> Vec<usize> {
+    let mut ans = vec![0; p.len()];
+    for (i, &v) in p.iter().enumerate() {
+        ans[v] = i;
+    }
+    ans
+}
+
+/// Applies `q` first and then `p`: `ans[i] = p[q[i]]`.
+pub fn compose(p: &[usize], q: &[usize]) -> Vec<usize> {
+    q.iter().map(|&v| p[v]).collect()
+}
+
+/// Cycles of `p`, each starting from its smallest element, ordered by it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::permutation::{cycles, parity};
+/// let p = [1, 2, 0, 4, 3, 5];
+/// assert_eq!(cycles(&p), vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+/// assert_eq!(parity(&p), 1);
+/// ```
+pub fn cycles(p: &[usize]) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; p.len()];
+    let mut ans = Vec::new();
+    for start in 0..p.len() {
+        if seen[start] {
+            continue;
+        }
+        let mut cycle = Vec::new();
+        let mut v = start;
+        while !seen[v] {
+            seen[v] = true;
+            cycle.push(v);
+            v = p[v];
+        }
+        ans.push(cycle);
+    }
+    ans
+}
+
+/// `0` for even permutations, `1` for odd ones.
+pub fn parity(p: &[usize]) -> usize {
+    (p.len() - cycles(p).len()) % 2
+}
+
+/// `p` applied `k` times, in `O(n)` for any `k`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::permutation::{compose, power};
+/// let p = [1, 2, 0, 4, 3];
+/// assert_eq!(power(&p, 2), compose(&p, &p));
+/// assert_eq!(power(&p, 6), vec![0, 1, 2, 3, 4]);
+/// assert_eq!(power(&p, 600_000_000_000_000_001), p.to_vec());
+/// ```
+pub fn power(p: &[usize], k: u64) -> Vec<usize> {
+    let mut ans = vec![0; p.len()];
+    for cycle in cycles(p) {
+        let shift = (k % cycle.len() as u64) as usize;
+        for (j, &v) in cycle.iter().enumerate() {
+            ans[v] = cycle[(j + shift) % cycle.len()];
+        }
+    }
+    ans
+}
+
+/// Digits of the lexicographic rank of `p` in the factorial number system, in `O(n log n)`.
+///
+/// `code[i]` is the number of values smaller than `p[i]` which appear after it,
+/// so the rank is `sum(code[i] * (n - 1 - i)!)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::permutation::{from_lehmer_code, lehmer_code};
+/// assert_eq!(lehmer_code(&[2, 0, 3, 1]), vec![2, 0, 1, 0]);
+/// assert_eq!(from_lehmer_code(&[2, 0, 1, 0]), vec![2, 0, 3, 1]);
+/// ```
+pub fn lehmer_code(p: &[usize]) -> Vec<usize> {
+    let mut unused = Fenwick::from_slice(&vec![1_i64; p.len()]);
+    p.iter().map(|&v| {
+        unused.add(v, -1);
+        unused.prefix_sum(v) as usize
+    }).collect()
+}
+
+/// Inverse of `lehmer_code`, in `O(n log n)`.
+pub fn from_lehmer_code(code: &[usize]) -> Vec<usize> {
+    let mut unused = Fenwick::from_slice(&vec![1_i64; code.len()]);
+    code.iter().map(|&c| {
+        let v = unused.lower_bound(c as i64 + 1);
+        unused.add(v, -1);
+        v
+    }).collect()
+}
+
+/// Lexicographic rank of `p` among the permutations of `0..n`, for `n <= 20`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::permutation::{rank, unrank};
+/// assert_eq!(rank(&[0, 1, 2]), 0);
+/// assert_eq!(rank(&[2, 1, 0]), 5);
+/// assert_eq!(unrank(4, 13), vec![2, 0, 3, 1]);
+/// let last: Vec<usize> = (0..20).rev().collect();
+/// assert_eq!(rank(&last), 2432902008176639999);
+/// assert_eq!(unrank(20, 2432902008176639999), last);
+/// ```
+pub fn rank(p: &[usize]) -> u64 {
+    lehmer_code(p).iter().enumerate()
+        .fold(0, |acc, (i, &c)| acc * (p.len() - i) as u64 + c as u64)
+}
+
+/// Permutation of `0..n` with the given lexicographic rank, for `n <= 20`.
+pub fn unrank(n: usize, mut rank: u64) -> Vec<usize> {
+    let mut code = vec![0; n];
+    for i in (0..n).rev() {
+        let base = (n - i) as u64;
+        code[i] = (rank % base) as usize;
+        rank /= base;
+    }
+    from_lehmer_code(&code)
+}
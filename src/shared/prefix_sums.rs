@@ -0,0 +1,229 @@
+use std::ops::{Add, Range, Sub};
+
+/// Sums of all prefixes of a sequence, for `O(1)` range sums.
+///
+/// Works for any type where `Default` is zero, including modular integers.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::prefix_sums::PrefixSums;
+/// let s = PrefixSums::new(&[3, -1, 4, 1, 5]);
+/// assert_eq!((s.sum(1..4), s.sum(0..5), s.sum(2..2)), (4, 12, 0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixSums<T> {
+    sums: Vec<T>,
+}
+
+impl<T> PrefixSums<T>
+    where T: Copy + Default + Add<Output=T> + Sub<Output=T> {
+    pub fn new(values: &[T]) -> PrefixSums<T> {
+        let mut sums = Vec::with_capacity(values.len() + 1);
+        sums.push(T::default());
+        for (i, &v) in values.iter().enumerate() {
+            let next = sums[i] + v;
+            sums.push(next);
+        }
+        PrefixSums{sums}
+    }
+
+    /// Number of summed values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sums.len() - 1
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sum of the first `end` values.
+    #[inline]
+    pub fn prefix(&self, end: usize) -> T {
+        self.sums[end]
+    }
+
+    #[inline]
+    pub fn sum(&self, range: Range<usize>) -> T {
+        self.sums[range.end] - self.sums[range.start]
+    }
+}
+
+/// Sums of all top-left submatrices of a table, for `O(1)` rectangle sums.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::prefix_sums::PrefixSums2d;
+/// let s = PrefixSums2d::new(&[vec![1, 2, 3], vec![4, 5, 6]]);
+/// assert_eq!((s.sum(0..2, 1..3), s.sum(1..2, 0..3), s.sum(0..0, 0..3)), (16, 15, 0));
+///
+/// let map = vec![vec!['.', '*', '*'], vec!['*', '.', '*']];
+/// let stars = PrefixSums2d::count(&map, |c| c == '*');
+/// assert_eq!((stars.sum(0..2, 0..2), stars.sum(0..2, 2..3)), (2, 2));
+///
+/// let corner = PrefixSums2d::count(&[vec![1, 0, 0], vec![0, 0, 0], vec![0, 0, 0]], |c| c == 1);
+/// assert_eq!((corner.sum(1..3, 1..3), corner.sum(0..3, 0..3)), (0, 1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixSums2d<T> {
+    height: usize,
+    width: usize,
+    sums: Vec<T>,
+}
+
+impl<T> PrefixSums2d<T>
+    where T: Copy + Default + Add<Output=T> + Sub<Output=T> {
+    /// Panics if the rows have different lengths.
+    pub fn new(rows: &[Vec<T>]) -> PrefixSums2d<T> {
+        PrefixSums2d::from_fn(rows, |&v| v)
+    }
+
+    /// Sums of `closure` over the cells of `rows`, e.g. of the output of `read2dchar!`.
+    pub fn from_fn<N, F>(rows: &[Vec<N>], closure: F) -> PrefixSums2d<T>
+        where F: Fn(&N) -> T {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let stride = width + 1;
+        let mut sums = vec![T::default(); (height + 1) * stride];
+        for (y, row) in rows.iter().enumerate() {
+            assert_eq!(row.len(), width, "Rows have different lengths");
+            for (x, cell) in row.iter().enumerate() {
+                sums[(y + 1) * stride + x + 1] = sums[y * stride + x + 1] + sums[(y + 1) * stride + x]
+                    - sums[y * stride + x] + closure(cell);
+            }
+        }
+        PrefixSums2d{height, width, sums}
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Sum over the cells in `rows` x `cols`.
+    #[inline]
+    pub fn sum(&self, rows: Range<usize>, cols: Range<usize>) -> T {
+        let stride = self.width + 1;
+        // Added first, so unsigned sums never go below zero on the way
+        (self.sums[rows.end * stride + cols.end] + self.sums[rows.start * stride + cols.start])
+            - self.sums[rows.start * stride + cols.end] - self.sums[rows.end * stride + cols.start]
+    }
+}
+
+impl PrefixSums2d<usize> {
+    /// Counts of the cells of `rows` satisfying `pred`.
+    #[inline]
+    pub fn count<N: Copy, F>(rows: &[Vec<N>], pred: F) -> PrefixSums2d<usize>
+        where F: Fn(N) -> bool {
+        PrefixSums2d::from_fn(rows, |&cell| pred(cell) as usize)
+    }
+}
+
+/// Offline additions on ranges, applied all at once by `build`.
+///
+/// Every range end gets `-value`, so `T` must be signed or wrap around like `ModInt`;
+/// unsigned integers underflow.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::prefix_sums::DifferenceArray;
+/// let mut d = DifferenceArray::new(5);
+/// d.add(1..4, 2);
+/// d.add(0..2, -1);
+/// d.add(4..5, 7);
+/// assert_eq!(d.build(), vec![-1, 1, 2, 2, 7]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifferenceArray<T> {
+    diff: Vec<T>,
+}
+
+impl<T> DifferenceArray<T>
+    where T: Copy + Default + Add<Output=T> + Sub<Output=T> {
+    #[inline]
+    pub fn new(n: usize) -> DifferenceArray<T> {
+        DifferenceArray{diff: vec![T::default(); n + 1]}
+    }
+
+    #[inline]
+    pub fn add(&mut self, range: Range<usize>, value: T) {
+        self.diff[range.start] = self.diff[range.start] + value;
+        self.diff[range.end] = self.diff[range.end] - value;
+    }
+
+    pub fn build(&self) -> Vec<T> {
+        let mut current = T::default();
+        self.diff[..self.diff.len() - 1].iter().map(|&d| {
+            current = current + d;
+            current
+        }).collect()
+    }
+}
+
+/// Offline additions on rectangles, applied all at once by `build`.
+///
+/// As with `DifferenceArray`, `T` must be signed or wrap around like `ModInt`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::prefix_sums::DifferenceArray2d;
+/// let mut d = DifferenceArray2d::new(2, 3);
+/// d.add(0..2, 1..3, 1);
+/// d.add(1..2, 0..2, 5);
+/// assert_eq!(d.build(), vec![vec![0, 1, 1], vec![5, 6, 1]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifferenceArray2d<T> {
+    height: usize,
+    width: usize,
+    diff: Vec<T>,
+}
+
+impl<T> DifferenceArray2d<T>
+    where T: Copy + Default + Add<Output=T> + Sub<Output=T> {
+    #[inline]
+    pub fn new(height: usize, width: usize) -> DifferenceArray2d<T> {
+        DifferenceArray2d{height, width, diff: vec![T::default(); (height + 1) * (width + 1)]}
+    }
+
+    pub fn add(&mut self, rows: Range<usize>, cols: Range<usize>, value: T) {
+        let stride = self.width + 1;
+        let corners = [(rows.start, cols.start, true), (rows.start, cols.end, false),
+                       (rows.end, cols.start, false), (rows.end, cols.end, true)];
+        for &(y, x, positive) in &corners {
+            let cell = &mut self.diff[y * stride + x];
+            *cell = if positive { *cell + value } else { *cell - value };
+        }
+    }
+
+    pub fn build(&self) -> Vec<Vec<T>> {
+        let stride = self.width + 1;
+        let mut ans = vec![vec![T::default(); self.width]; self.height];
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let mut value = self.diff[y * stride + x];
+                if y > 0 {
+                    value = value + ans[y - 1][x];
+                }
+                if x > 0 {
+                    value = value + ans[y][x - 1];
+                }
+                if x > 0 && y > 0 {
+                    value = value - ans[y - 1][x - 1];
+                }
+                ans[y][x] = value;
+            }
+        }
+        ans
+    }
+}
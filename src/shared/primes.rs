@@ -0,0 +1,326 @@
+use std::mem::swap;
+use shared::integers::{ceil_div, isqrt};
+use shared::modular::Montgomery;
+
+/// Linear sieve of Euler: primes, smallest prime factors, Euler's totient and the Möbius function up to `n`
+/// in `O(n)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::primes::Sieve;
+/// let sieve = Sieve::new(100);
+/// assert_eq!(sieve.primes().len(), 25);
+/// assert_eq!(&sieve.primes()[..6], &[2, 3, 5, 7, 11, 13]);
+/// assert!(sieve.is_prime(97) && !sieve.is_prime(91) && !sieve.is_prime(1) && !sieve.is_prime(0));
+/// assert_eq!((sieve.smallest_factor(91), sieve.smallest_factor(97)), (7, 97));
+/// assert_eq!((sieve.phi(1), sieve.phi(36), sieve.phi(97)), (1, 12, 96));
+/// assert_eq!((sieve.mu(1), sieve.mu(30), sieve.mu(12), sieve.mu(97)), (1, -1, 0, -1));
+/// assert_eq!(sieve.factorize(72), vec![(2, 3), (3, 2)]);
+/// assert!(sieve.factorize(1).is_empty());
+///
+/// let gcd = |mut a: u32, mut b: u32| { while b != 0 { a %= b; std::mem::swap(&mut a, &mut b); } a };
+/// for x in 1..=100 {
+///     assert_eq!(sieve.phi(x), (1..=x as u32).filter(|&y| gcd(x as u32, y) == 1).count() as u32);
+///     let mu_sum: i32 = (1..=x).filter(|&d| x % d == 0).map(|d| sieve.mu(d) as i32).sum();
+///     assert_eq!(mu_sum, (x == 1) as i32);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sieve {
+    primes: Vec<u32>,
+    smallest: Vec<u32>,
+    phi: Vec<u32>,
+    mu: Vec<i8>,
+}
+
+impl Sieve {
+    pub fn new(n: usize) -> Sieve {
+        let mut primes: Vec<u32> = Vec::new();
+        let mut smallest = vec![0; n + 1];
+        let mut phi = vec![0; n + 1];
+        let mut mu = vec![0; n + 1];
+        if n >= 1 {
+            phi[1] = 1;
+            mu[1] = 1;
+        }
+        for i in 2..=n {
+            if smallest[i] == 0 {
+                smallest[i] = i as u32;
+                phi[i] = i as u32 - 1;
+                mu[i] = -1;
+                primes.push(i as u32);
+            }
+            // Every composite is crossed out once, by its smallest prime factor
+            for &p in &primes {
+                let multiple = i * p as usize;
+                if p > smallest[i] || multiple > n {
+                    break;
+                }
+                smallest[multiple] = p;
+                if p == smallest[i] {
+                    phi[multiple] = phi[i] * p;
+                } else {
+                    phi[multiple] = phi[i] * (p - 1);
+                    mu[multiple] = -mu[i];
+                }
+            }
+        }
+        Sieve{primes, smallest, phi, mu}
+    }
+
+    /// All primes up to `n` in increasing order.
+    #[inline]
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    #[inline]
+    pub fn is_prime(&self, x: usize) -> bool {
+        x >= 2 && self.smallest[x] as usize == x
+    }
+
+    /// Smallest prime factor of `x >= 2`.
+    #[inline]
+    pub fn smallest_factor(&self, x: usize) -> u32 {
+        self.smallest[x]
+    }
+
+    /// Euler's totient of `x >= 1`.
+    #[inline]
+    pub fn phi(&self, x: usize) -> u32 {
+        self.phi[x]
+    }
+
+    /// Möbius function of `x >= 1`.
+    #[inline]
+    pub fn mu(&self, x: usize) -> i8 {
+        self.mu[x]
+    }
+
+    /// Prime factors of `x >= 1` with their exponents, in increasing order, in `O(log x)`.
+    pub fn factorize(&self, mut x: usize) -> Vec<(u32, u32)> {
+        let mut ans: Vec<(u32, u32)> = Vec::new();
+        while x > 1 {
+            let p = self.smallest[x];
+            match ans.last_mut() {
+                Some(last) if last.0 == p => last.1 += 1,
+                _ => ans.push((p, 1)),
+            }
+            x /= p as usize;
+        }
+        ans
+    }
+}
+
+/// Primes in `low..high` by sieving the range with the primes up to `sqrt(high)`.
+///
+/// Takes `O(sqrt(high) + (high - low) log log high)`, so ranges of a few million near `10^12` are fine.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::primes::{is_prime, segmented_sieve};
+/// assert_eq!(segmented_sieve(0, 30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+/// assert_eq!(segmented_sieve(90, 97), vec![]);
+/// assert_eq!(segmented_sieve(97, 98), vec![97]);
+/// let low = 1_000_000_000_000;
+/// let primes = segmented_sieve(low, low + 100_000);
+/// assert_eq!(primes[0], 1_000_000_000_039);
+/// assert_eq!(primes, (low..low + 100_000).filter(|&x| is_prime(x)).collect::<Vec<_>>());
+/// ```
+pub fn segmented_sieve(low: u64, high: u64) -> Vec<u64> {
+    if high <= low {
+        return Vec::new();
+    }
+    let mut composite = vec![false; (high - low) as usize];
+    for &p in Sieve::new(isqrt(high - 1) as usize).primes() {
+        let p = p as u64;
+        // Near u64::MAX the first multiple may not exist, and stepping stops before overflowing
+        let first = match ceil_div(low, p).checked_mul(p) {
+            Some(first) => first,
+            None => continue,
+        };
+        for multiple in ((p * p).max(first)..high).step_by(p as usize) {
+            composite[(multiple - low) as usize] = true;
+        }
+    }
+    (low.max(2)..high).filter(|&x| !composite[(x - low) as usize]).collect()
+}
+
+/// Deterministic Miller–Rabin test for any `u64`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::primes::{is_prime, Sieve};
+/// let sieve = Sieve::new(10_000);
+/// assert!((0..=10_000).all(|x| is_prime(x as u64) == sieve.is_prime(x)));
+/// assert!(is_prime((1 << 61) - 1) && is_prime(u64::MAX - 58) && is_prime(1_000_000_007));
+/// // Carmichael numbers and strong pseudoprimes to several small bases
+/// assert!(!is_prime(561) && !is_prime(3_215_031_751) && !is_prime(3_825_123_056_546_413_051));
+/// assert!(!is_prime(u64::MAX) && !is_prime(4_294_967_291 * 4_294_967_291));
+/// ```
+pub fn is_prime(n: u64) -> bool {
+    for &p in &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+    if n < 37 * 37 {
+        return n > 1;
+    }
+    let mont = Montgomery::new(n);
+    let (one, minus_one) = (mont.one(), mont.transform(n - 1));
+    let shift = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> shift;
+    // These bases are enough for every n below 2^64
+    'bases: for &base in &[2, 325, 9375, 28_178, 450_775, 9_780_504, 1_795_265_022] {
+        let mut x = mont.pow(mont.transform(base), odd);
+        if base % n == 0 || x == one || x == minus_one {
+            continue;
+        }
+        for _ in 1..shift {
+            x = mont.mul(x, x);
+            if x == minus_one {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Binary greatest common divisor.
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return a | b;
+    }
+    let shift = (a | b).trailing_zeros();
+    a >>= a.trailing_zeros();
+    while b != 0 {
+        b >>= b.trailing_zeros();
+        if a > b {
+            swap(&mut a, &mut b);
+        }
+        b -= a;
+    }
+    a << shift
+}
+
+/// A non-trivial factor of an odd composite `n`, by Pollard's rho with Brent's cycle detection.
+fn pollard_rho(n: u64) -> u64 {
+    // Differences are multiplied together, so a gcd is taken once per this many steps
+    const BATCH: u64 = 128;
+    let mont = Montgomery::new(n);
+    let mut increment = mont.one();
+    loop {
+        let next = |x: u64| mont.add(mont.mul(x, x), increment);
+        let (mut x, mut y, mut saved) = (0, mont.transform(2), 0);
+        let (mut length, mut product, mut g) = (1, mont.one(), 1);
+        while g == 1 {
+            x = y;
+            for _ in 0..length {
+                y = next(y);
+            }
+            let mut done = 0;
+            while done < length && g == 1 {
+                saved = y;
+                for _ in 0..BATCH.min(length - done) {
+                    y = next(y);
+                    product = mont.mul(product, mont.sub(x, y));
+                }
+                // Montgomery form only multiplies by a unit, so the gcd is the same
+                g = gcd(product, n);
+                done += BATCH;
+            }
+            length *= 2;
+        }
+        if g == n {
+            // The batch overshot, so step through it again one by one
+            g = 1;
+            while g == 1 {
+                saved = next(saved);
+                g = gcd(mont.sub(x, saved), n);
+            }
+        }
+        if g != n {
+            return g;
+        }
+        increment = mont.add(increment, mont.one());
+    }
+}
+
+/// Prime factors of `n >= 1` with their exponents, in increasing order.
+///
+/// Small factors are found by trial division and the rest by Pollard's rho, in about `O(n^(1/4))`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::primes::{divisors, factorize};
+/// assert_eq!(factorize(1), vec![]);
+/// assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// assert_eq!(factorize(600_851_475_143), vec![(71, 1), (839, 1), (1471, 1), (6857, 1)]);
+/// assert_eq!(factorize(u64::MAX), vec![(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65537, 1), (6_700_417, 1)]);
+/// assert_eq!(factorize(998_244_353 * 1_000_000_007), vec![(998_244_353, 1), (1_000_000_007, 1)]);
+/// assert_eq!(factorize(4_294_967_291 * 4_294_967_291), vec![(4_294_967_291, 2)]);
+/// assert_eq!(factorize(1 << 63), vec![(2, 63)]);
+/// assert_eq!(factorize(3_u64.pow(40)), vec![(3, 40)]);
+///
+/// assert_eq!(divisors(&factorize(60)), vec![1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60]);
+/// assert_eq!(divisors(&factorize(963_761_198_400)).len(), 6720);
+/// assert_eq!(divisors(&[]), vec![1]);
+/// ```
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "Zero has no factorization");
+    let mut ans: Vec<(u64, u32)> = Vec::new();
+    let mut p = 2;
+    while p < 100 && p * p <= n {
+        if n % p == 0 {
+            let mut exp = 0;
+            while n % p == 0 {
+                n /= p;
+                exp += 1;
+            }
+            ans.push((p, exp));
+        }
+        p += 1;
+    }
+    let mut primes = Vec::new();
+    let mut stack = if n > 1 { vec![n] } else { Vec::new() };
+    while let Some(x) = stack.pop() {
+        if is_prime(x) {
+            primes.push(x);
+        } else {
+            let factor = pollard_rho(x);
+            stack.push(factor);
+            stack.push(x / factor);
+        }
+    }
+    primes.sort_unstable();
+    for p in primes {
+        match ans.last_mut() {
+            Some(last) if last.0 == p => last.1 += 1,
+            _ => ans.push((p, 1)),
+        }
+    }
+    ans
+}
+
+/// All divisors in increasing order of the number with the given factorization.
+pub fn divisors(factors: &[(u64, u32)]) -> Vec<u64> {
+    let mut ans = vec![1];
+    for &(p, exp) in factors {
+        let len = ans.len();
+        let mut power = 1;
+        for _ in 0..exp {
+            power *= p;
+            for i in 0..len {
+                ans.push(ans[i] * power);
+            }
+        }
+    }
+    ans.sort_unstable();
+    ans
+}
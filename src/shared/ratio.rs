@@ -0,0 +1,309 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Error};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use std::str::FromStr;
+use shared::bigints::BigInt;
+
+/// Signed integers a `Ratio` can be made of.
+pub trait RatioInteger: Clone + Ord + FromStr + Display + Add<Output=Self> + Sub<Output=Self>
+    + Mul<Output=Self> + Div<Output=Self> + Rem<Output=Self> + Neg<Output=Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// Exact comparison of `a * b` with `c * d`, even if the products overflow `Self`.
+    fn cmp_products(a: &Self, b: &Self, c: &Self, d: &Self) -> Ordering;
+    /// Nearest `f64` to `numer / denom` for a positive `denom`.
+    fn div_to_f64(numer: &Self, denom: &Self) -> f64;
+}
+
+impl RatioInteger for i64 {
+    #[inline]
+    fn zero() -> i64 {
+        0
+    }
+
+    #[inline]
+    fn one() -> i64 {
+        1
+    }
+
+    #[inline]
+    fn cmp_products(a: &i64, b: &i64, c: &i64, d: &i64) -> Ordering {
+        (*a as i128 * *b as i128).cmp(&(*c as i128 * *d as i128))
+    }
+
+    #[inline]
+    fn div_to_f64(numer: &i64, denom: &i64) -> f64 {
+        *numer as f64 / *denom as f64
+    }
+}
+
+impl RatioInteger for i128 {
+    #[inline]
+    fn zero() -> i128 {
+        0
+    }
+
+    #[inline]
+    fn one() -> i128 {
+        1
+    }
+
+    fn cmp_products(a: &i128, b: &i128, c: &i128, d: &i128) -> Ordering {
+        match (a.checked_mul(*b), c.checked_mul(*d)) {
+            (Some(left), Some(right)) => left.cmp(&right),
+            _ => (BigInt::from(*a) * BigInt::from(*b)).cmp(&(BigInt::from(*c) * BigInt::from(*d))),
+        }
+    }
+
+    #[inline]
+    fn div_to_f64(numer: &i128, denom: &i128) -> f64 {
+        *numer as f64 / *denom as f64
+    }
+}
+
+impl RatioInteger for BigInt {
+    #[inline]
+    fn zero() -> BigInt {
+        BigInt::zero()
+    }
+
+    #[inline]
+    fn one() -> BigInt {
+        BigInt::from(1)
+    }
+
+    #[inline]
+    fn cmp_products(a: &BigInt, b: &BigInt, c: &BigInt, d: &BigInt) -> Ordering {
+        (a * b).cmp(&(c * d))
+    }
+
+    /// Scales the numerator to keep 64 significant bits in the quotient, so huge parts do not overflow `f64`.
+    fn div_to_f64(numer: &BigInt, denom: &BigInt) -> f64 {
+        let shift = (denom.bits() + 64).saturating_sub(numer.bits());
+        let quotient = (numer.clone() << shift) / denom;
+        quotient.to_f64() * 0.5_f64.powi(shift.min(i32::MAX as u64) as i32)
+    }
+}
+
+/// Non-negative greatest common divisor.
+pub fn gcd<T: RatioInteger>(mut a: T, mut b: T) -> T {
+    while b != T::zero() {
+        let r = a % b.clone();
+        a = b;
+        b = r;
+    }
+    if a < T::zero() { -a } else { a }
+}
+
+/// Exact fraction kept in lowest terms with a positive denominator.
+///
+/// Comparison never overflows; arithmetic on `i64` and `i128` may overflow like the integers themselves,
+/// though common factors are cancelled before multiplying.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate olympic_lib;
+/// # use olympic_lib::shared::ratio::Ratio;
+/// # use olympic_lib::shared::bigints::BigInt;
+/// # fn main() {
+/// let input = &mut &b"1/6 -3/4\n"[..];
+/// readln!(input; a: Ratio<i64>, b: Ratio<i64>);
+/// assert_eq!(a + b, Ratio::new(-7, 12));
+/// assert_eq!((a * b).to_string(), "-1/8");
+/// assert_eq!(a / b, Ratio::new(2, -9));
+/// assert_eq!(Ratio::new(4_i64, 2).to_string(), "2");
+/// assert!(b < a && -b > a);
+///
+/// // i64 cross products overflow here, the comparison stays exact
+/// let big = Ratio::new(i64::MAX, i64::MAX - 1);
+/// let bigger = Ratio::new(i64::MAX - 1, i64::MAX - 2);
+/// assert!(big < bigger);
+/// assert_eq!((bigger.floor(), bigger.ceil()), (1, 2));
+/// assert_eq!(Ratio::new(-7_i64, 2).floor(), -4);
+///
+/// let third: Ratio<BigInt> = "1/3".parse().unwrap();
+/// let sum = (0..100).fold(Ratio::from(BigInt::zero()), |acc, _| acc + third.clone());
+/// assert_eq!(sum.to_string(), "100/3");
+/// assert!((sum.to_f64() - 33.333333333333336).abs() < 1e-12);
+/// assert!("1/0".parse::<Ratio<i128>>().is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio<T> {
+    numer: T,
+    denom: T,
+}
+
+/// An error when parsing a `Ratio` from something other than `p` or `p/q` with a non-zero `q`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRatioError;
+
+impl Display for ParseRatioError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str("invalid fraction")
+    }
+}
+
+impl<T: RatioInteger> Ratio<T> {
+    /// `numer / denom` in lowest terms, panics on a zero denominator.
+    pub fn new(numer: T, denom: T) -> Ratio<T> {
+        assert!(denom != T::zero(), "Zero denominator");
+        let g = gcd(numer.clone(), denom.clone());
+        let (numer, denom) = (numer / g.clone(), denom / g);
+        if denom < T::zero() {
+            Ratio{numer: -numer, denom: -denom}
+        } else {
+            Ratio{numer, denom}
+        }
+    }
+
+    #[inline]
+    pub fn numer(&self) -> &T {
+        &self.numer
+    }
+
+    #[inline]
+    pub fn denom(&self) -> &T {
+        &self.denom
+    }
+
+    #[inline]
+    pub fn is_integer(&self) -> bool {
+        self.denom == T::one()
+    }
+
+    #[inline]
+    pub fn abs(&self) -> Ratio<T> {
+        if self.numer < T::zero() { -self.clone() } else { self.clone() }
+    }
+
+    /// `1 / self`, panics on zero.
+    #[inline]
+    pub fn recip(&self) -> Ratio<T> {
+        Ratio::new(self.denom.clone(), self.numer.clone())
+    }
+
+    /// Largest integer not greater than `self`.
+    pub fn floor(&self) -> T {
+        let quotient = self.numer.clone() / self.denom.clone();
+        if self.numer < T::zero() && !self.is_integer() { quotient - T::one() } else { quotient }
+    }
+
+    /// Smallest integer not less than `self`.
+    pub fn ceil(&self) -> T {
+        let quotient = self.numer.clone() / self.denom.clone();
+        if self.numer > T::zero() && !self.is_integer() { quotient + T::one() } else { quotient }
+    }
+
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        T::div_to_f64(&self.numer, &self.denom)
+    }
+}
+
+impl<T: RatioInteger> From<T> for Ratio<T> {
+    #[inline]
+    fn from(value: T) -> Ratio<T> {
+        Ratio{numer: value, denom: T::one()}
+    }
+}
+
+impl<T: RatioInteger> Ord for Ratio<T> {
+    #[inline]
+    fn cmp(&self, other: &Ratio<T>) -> Ordering {
+        T::cmp_products(&self.numer, &other.denom, &other.numer, &self.denom)
+    }
+}
+
+impl<T: RatioInteger> PartialOrd for Ratio<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Ratio<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: RatioInteger> FromStr for Ratio<T> {
+    type Err = ParseRatioError;
+    fn from_str(s: &str) -> Result<Ratio<T>, ParseRatioError> {
+        let mut parts = s.splitn(2, '/');
+        let numer = parts.next().unwrap_or("").parse().map_err(|_| ParseRatioError)?;
+        let denom = match parts.next() {
+            Some(denom) => denom.parse().map_err(|_| ParseRatioError)?,
+            None => T::one(),
+        };
+        if denom == T::zero() {
+            return Err(ParseRatioError);
+        }
+        Ok(Ratio::new(numer, denom))
+    }
+}
+
+/// Prints `p/q`, or just `p` for integers.
+impl<T: RatioInteger> Display for Ratio<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+impl<T: RatioInteger> Neg for Ratio<T> {
+    type Output = Ratio<T>;
+    #[inline]
+    fn neg(self) -> Ratio<T> {
+        Ratio{numer: -self.numer, denom: self.denom}
+    }
+}
+
+impl<T: RatioInteger> Add for Ratio<T> {
+    type Output = Ratio<T>;
+    fn add(self, other: Ratio<T>) -> Ratio<T> {
+        let g = gcd(self.denom.clone(), other.denom.clone());
+        let (left, right) = (self.denom.clone() / g.clone(), other.denom / g);
+        Ratio::new(self.numer * right.clone() + other.numer * left, self.denom * right)
+    }
+}
+
+impl<T: RatioInteger> Sub for Ratio<T> {
+    type Output = Ratio<T>;
+    #[inline]
+    fn sub(self, other: Ratio<T>) -> Ratio<T> {
+        self + -other
+    }
+}
+
+impl<T: RatioInteger> Mul for Ratio<T> {
+    type Output = Ratio<T>;
+    fn mul(self, other: Ratio<T>) -> Ratio<T> {
+        let g1 = gcd(self.numer.clone(), other.denom.clone());
+        let g2 = gcd(other.numer.clone(), self.denom.clone());
+        Ratio{numer: self.numer / g1.clone() * (other.numer / g2.clone()),
+              denom: self.denom / g2 * (other.denom / g1)}
+    }
+}
+
+impl<T: RatioInteger> Div for Ratio<T> {
+    type Output = Ratio<T>;
+    #[inline]
+    fn div(self, other: Ratio<T>) -> Ratio<T> {
+        self.mul(other.recip())
+    }
+}
+
+macro_rules! forward_assign {
+    ($($assign_imp:ident, $assign_method:ident, $method:ident);*) => ($(
+        impl<T: RatioInteger> $assign_imp for Ratio<T> {
+            #[inline]
+            fn $assign_method(&mut self, other: Ratio<T>) {
+                *self = self.clone().$method(other);
+            }
+        }
+    )*);
+}
+
+forward_assign!(AddAssign, add_assign, add; SubAssign, sub_assign, sub;
+                MulAssign, mul_assign, mul; DivAssign, div_assign, div);
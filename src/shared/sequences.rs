@@ -0,0 +1,204 @@
+use shared::slices_extensions::partition_point;
+
+/// Indices of a longest strictly increasing subsequence of `x`, in `O(n log n)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::sequences::{longest_increasing, longest_non_decreasing};
+/// let x = [3, 1, 2, 2, 5, 4, 4];
+/// assert_eq!(longest_increasing(&x), vec![1, 3, 6]);
+/// assert_eq!(longest_non_decreasing(&x), vec![1, 2, 3, 5, 6]);
+/// assert!(longest_increasing::<i32>(&[]).is_empty());
+/// ```
+pub fn longest_increasing<T: PartialOrd>(x: &[T]) -> Vec<usize> {
+    longest_chain(x, |a, b| a < b)
+}
+
+/// Indices of a longest non-decreasing subsequence of `x`, in `O(n log n)`.
+pub fn longest_non_decreasing<T: PartialOrd>(x: &[T]) -> Vec<usize> {
+    longest_chain(x, |a, b| a <= b)
+}
+
+/// Patience sorting: `tails[len]` is the index of the smallest end of a chain of length `len + 1`.
+fn longest_chain<T, F>(x: &[T], before: F) -> Vec<usize>
+    where F: Fn(&T, &T) -> bool {
+    let mut tails: Vec<usize> = Vec::new();
+    let mut parent = vec![None; x.len()];
+    for i in 0..x.len() {
+        let len = partition_point(&tails, |&j| before(&x[j], &x[i]));
+        parent[i] = if len > 0 { Some(tails[len - 1]) } else { None };
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+    let mut ans = Vec::with_capacity(tails.len());
+    let mut current = tails.last().cloned();
+    while let Some(i) = current {
+        ans.push(i);
+        current = parent[i];
+    }
+    ans.reverse();
+    ans
+}
+
+/// Index pairs `(i, j)` with `a[i] == b[j]` forming a longest common subsequence, in `O(nm)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::sequences::longest_common;
+/// let (a, b) = (b"ABCBDAB", b"BDCABA");
+/// let pairs = longest_common(a, b);
+/// assert_eq!(pairs.len(), 4);
+/// assert!(pairs.iter().all(|&(i, j)| a[i] == b[j]));
+/// assert!(pairs.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+/// ```
+pub fn longest_common<T: PartialEq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let stride = b.len() + 1;
+    // lengths[i * stride + j] is the answer for the suffixes a[i..] and b[j..]
+    let mut lengths = vec![0_u32; (a.len() + 1) * stride];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i * stride + j] = if a[i] == b[j] {
+                lengths[(i + 1) * stride + j + 1] + 1
+            } else {
+                lengths[(i + 1) * stride + j].max(lengths[i * stride + j + 1])
+            };
+        }
+    }
+    let mut ans = Vec::with_capacity(lengths[0] as usize);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            ans.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * stride + j] >= lengths[i * stride + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    ans
+}
+
+/// Number of pairs `i < j` with `x[i] > x[j]`, by merge sort in `O(n log n)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::sequences::count_inversions;
+/// assert_eq!(count_inversions(&[3, 1, 2, 2, 0]), 7);
+/// assert_eq!(count_inversions(&[1, 1, 1]), 0);
+/// let reversed: Vec<u32> = (0..1000).rev().collect();
+/// assert_eq!(count_inversions(&reversed), 1000 * 999 / 2);
+/// ```
+pub fn count_inversions<T: Ord + Clone>(x: &[T]) -> u64 {
+    let mut values = x.to_vec();
+    let mut buffer = x.to_vec();
+    merge_count(&mut values, &mut buffer)
+}
+
+fn merge_count<T: Ord + Clone>(values: &mut [T], buffer: &mut [T]) -> u64 {
+    let n = values.len();
+    if n < 2 {
+        return 0;
+    }
+    let mid = n / 2;
+    let mut ans = merge_count(&mut values[..mid], &mut buffer[..mid])
+        + merge_count(&mut values[mid..], &mut buffer[mid..]);
+    let (mut l, mut r) = (0, mid);
+    for slot in buffer.iter_mut() {
+        if r == n || (l < mid && values[l] <= values[r]) {
+            *slot = values[l].clone();
+            l += 1;
+        } else {
+            *slot = values[r].clone();
+            ans += (mid - l) as u64;
+            r += 1;
+        }
+    }
+    values.clone_from_slice(buffer);
+    ans
+}
+
+/// The `k`-th smallest item of `x` counting from zero, by quickselect in expected `O(n)`.
+///
+/// Reorders `x` so that smaller items come before position `k` and greater ones after it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::sequences::kth_smallest;
+/// let mut x = [5, 1, 4, 1, 5, 9, 2, 6];
+/// assert_eq!(*kth_smallest(&mut x, 0), 1);
+/// assert_eq!(*kth_smallest(&mut x, 4), 5);
+/// assert_eq!(*kth_smallest(&mut x, 7), 9);
+/// assert!(x[..4].iter().all(|&v| v <= 5) && x[5..].iter().all(|&v| v >= 5));
+/// ```
+pub fn kth_smallest<T: Ord>(x: &mut [T], k: usize) -> &T {
+    assert!(k < x.len(), "k is out of range");
+    let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+    let (mut lo, mut hi) = (0, x.len());
+    while hi - lo > 1 {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        x.swap(lo, lo + (seed % (hi - lo) as u64) as usize);
+        // Three-way partition around x[lo]: [lo, lt) less, [lt, i) equal, (gt, hi) greater
+        let (mut lt, mut i, mut gt) = (lo, lo + 1, hi);
+        while i < gt {
+            if x[i] < x[lt] {
+                x.swap(i, lt);
+                lt += 1;
+                i += 1;
+            } else if x[i] > x[lt] {
+                gt -= 1;
+                x.swap(i, gt);
+            } else {
+                i += 1;
+            }
+        }
+        if k < lt {
+            hi = lt;
+        } else if k >= gt {
+            lo = gt;
+        } else {
+            break;
+        }
+    }
+    &x[k]
+}
+
+/// Index of an item occurring more than `n / 2` times, by Boyer–Moore voting in `O(n)`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::sequences::majority;
+/// assert_eq!(majority(&[2, 7, 2, 1, 2]).map(|i| [2, 7, 2, 1, 2][i]), Some(2));
+/// assert_eq!(majority(&[1, 2, 1, 2]), None);
+/// assert_eq!(majority::<u8>(&[]), None);
+/// ```
+pub fn majority<T: PartialEq>(x: &[T]) -> Option<usize> {
+    let mut candidate = 0;
+    let mut votes = 0;
+    for i in 0..x.len() {
+        if votes == 0 {
+            candidate = i;
+            votes = 1;
+        } else if x[i] == x[candidate] {
+            votes += 1;
+        } else {
+            votes -= 1;
+        }
+    }
+    if votes > 0 && 2 * x.iter().filter(|&v| *v == x[candidate]).count() > x.len() {
+        Some(candidate)
+    } else {
+        None
+    }
+}
@@ -1,58 +1,635 @@
-trait Rotatable {
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use shared::string::MutableString;
+
+/// Cyclic shifts done in place, without allocating.
+///
+/// `rotate_forward` moves every item `steps` positions towards the end,
+/// `rotate_backward` towards the beginning. Rotating an empty sequence does nothing.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::Rotatable;
+/// # use olympic_lib::shared::string::MutableString;
+/// # use std::collections::VecDeque;
+/// let mut x = [1, 2, 3, 4, 5];
+/// x.rotate_forward(7);
+/// assert_eq!(x, [4, 5, 1, 2, 3]);
+/// x.rotate_backward(2);
+/// assert_eq!(x, [1, 2, 3, 4, 5]);
+/// x.rotate_range_forward(1..4, 1);
+/// assert_eq!(x, [1, 4, 2, 3, 5]);
+///
+/// let mut empty: [i32; 0] = [];
+/// empty.rotate_forward(3);
+///
+/// let mut d: VecDeque<_> = (1..5).collect();
+/// d.rotate_backward(1);
+/// assert_eq!(d, [2, 3, 4, 1]);
+///
+/// let mut s: MutableString = "abcde".parse().unwrap();
+/// s.rotate_range_backward(0..3, 1);
+/// assert_eq!(s.to_string(), "bcade");
+/// ```
+pub trait Rotatable {
     fn rotate_forward(&mut self, steps : usize);
     fn rotate_backward(&mut self, steps : usize);
+    fn rotate_range_forward(&mut self, range: Range<usize>, steps : usize);
+    fn rotate_range_backward(&mut self, range: Range<usize>, steps : usize);
 }
 
-impl<T: Clone> Rotatable for [T] {
+impl<T> Rotatable for [T] {
+    /// Three reversals: `[a b] -> [b' a'] -> [b a]`.
     fn rotate_forward(&mut self, steps : usize){
-        let clone = self.to_vec();
+        if self.is_empty() {
+            return;
+        }
         let mid : usize = steps % self.len();
-        let antimid : usize = self.len() - mid;
-        let (left, right) = clone.split_at(antimid);
-        self[..mid].clone_from_slice(right);
-        self[mid..].clone_from_slice(left);
+        self.reverse();
+        self[..mid].reverse();
+        self[mid..].reverse();
     }
     fn rotate_backward(&mut self, steps : usize){
-        let clone = self.to_vec();
+        if self.is_empty() {
+            return;
+        }
         let antimid : usize = steps % self.len();
-        let mid : usize = self.len() - antimid;
-        let (left, right) = clone.split_at(antimid);
-        self[..mid].clone_from_slice(right);
-        self[mid..].clone_from_slice(left);
+        self.rotate_forward(self.len() - antimid);
+    }
+    #[inline]
+    fn rotate_range_forward(&mut self, range: Range<usize>, steps : usize){
+        self[range].rotate_forward(steps)
+    }
+    #[inline]
+    fn rotate_range_backward(&mut self, range: Range<usize>, steps : usize){
+        self[range].rotate_backward(steps)
     }
 }
 
-fn bisect_right<T>(x: &[T], value: T) -> usize
-    where T: PartialOrd {
-    if x.len() == 0 || value < x[0] {
+impl<T> Rotatable for VecDeque<T> {
+    #[inline]
+    fn rotate_forward(&mut self, steps : usize){
+        self.make_contiguous().rotate_forward(steps)
+    }
+    #[inline]
+    fn rotate_backward(&mut self, steps : usize){
+        self.make_contiguous().rotate_backward(steps)
+    }
+    #[inline]
+    fn rotate_range_forward(&mut self, range: Range<usize>, steps : usize){
+        self.make_contiguous().rotate_range_forward(range, steps)
+    }
+    #[inline]
+    fn rotate_range_backward(&mut self, range: Range<usize>, steps : usize){
+        self.make_contiguous().rotate_range_backward(range, steps)
+    }
+}
+
+impl Rotatable for MutableString {
+    #[inline]
+    fn rotate_forward(&mut self, steps : usize){
+        self.as_mut_vec().rotate_forward(steps)
+    }
+    #[inline]
+    fn rotate_backward(&mut self, steps : usize){
+        self.as_mut_vec().rotate_backward(steps)
+    }
+    #[inline]
+    fn rotate_range_forward(&mut self, range: Range<usize>, steps : usize){
+        self[range].rotate_forward(steps)
+    }
+    #[inline]
+    fn rotate_range_backward(&mut self, range: Range<usize>, steps : usize){
+        self[range].rotate_backward(steps)
+    }
+}
+
+/// Integers sorted by `radix_sort`, as the unsigned key which orders them.
+pub trait RadixKey: Copy {
+    /// Number of low bytes of `radix_key` which can be non-zero.
+    const BYTES: usize;
+    fn radix_key(&self) -> u64;
+}
+
+macro_rules! impl_radix_key {
+    ($($t:ty => $bytes:expr, $flip:expr);*) => ($(
+        impl RadixKey for $t {
+            const BYTES: usize = $bytes;
+            #[inline]
+            fn radix_key(&self) -> u64 {
+                (*self as u64 ^ $flip) & (u64::MAX >> (64 - 8 * $bytes))
+            }
+        }
+    )*);
+}
+
+impl_radix_key!(u8 => 1, 0; u16 => 2, 0; u32 => 4, 0; u64 => 8, 0; usize => 8, 0;
+                i8 => 1, 1 << 7; i16 => 2, 1 << 15; i32 => 4, 1 << 31; i64 => 8, 1 << 63);
+
+/// Pairs are sorted by the key only, keeping the order of equal keys, e.g. `(key, index)`.
+impl<K: RadixKey, V: Copy> RadixKey for (K, V) {
+    const BYTES: usize = K::BYTES;
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        self.0.radix_key()
+    }
+}
+
+/// Sorting without comparisons, and sorting of indices instead of items.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::Sortable;
+/// let mut x = vec![5_i64, -3, 1 << 40, 0, -(1 << 50), 5];
+/// assert_eq!(x.argsort(), vec![4, 1, 3, 0, 5, 2]);
+/// assert_eq!(x.radix_argsort(), vec![4, 1, 3, 0, 5, 2]);
+/// x.radix_sort();
+/// assert_eq!(x, vec![-(1 << 50), -3, 0, 5, 5, 1 << 40]);
+///
+/// let mut pairs = vec![(3_u32, 0), (1, 1), (3, 2), (0, 3)];
+/// pairs.radix_sort();
+/// assert_eq!(pairs, vec![(0, 3), (1, 1), (3, 0), (3, 2)]);
+///
+/// let mut words = vec!["bb", "a", "ccc", "dd"];
+/// assert_eq!(words.argsort_by_key(|w| w.len()), vec![1, 0, 3, 2]);
+/// words.counting_sort_by_key(4, |w| w.len());
+/// assert_eq!(words, vec!["a", "bb", "dd", "ccc"]);
+/// ```
+pub trait Sortable<T> {
+    /// Stable LSD radix sort by bytes, in `O(n)` passes over at most `T::BYTES` digits.
+    fn radix_sort(&mut self) where T: RadixKey;
+    /// Stable sort by a small key in `0..bound`, in `O(n + bound)`.
+    fn counting_sort_by_key<F>(&mut self, bound: usize, key: F)
+        where T: Copy, F: Fn(&T) -> usize;
+    /// Indices of the items in stable sorted order.
+    fn argsort(&self) -> Vec<usize> where T: Ord;
+    /// `argsort` by `key`, which is computed once per item.
+    fn argsort_by_key<K, F>(&self, key: F) -> Vec<usize>
+        where K: Ord, F: FnMut(&T) -> K;
+    /// `argsort` done with `radix_sort`.
+    fn radix_argsort(&self) -> Vec<usize> where T: RadixKey;
+}
+
+impl<T> Sortable<T> for [T] {
+    fn radix_sort(&mut self) where T: RadixKey {
+        let mut from = self.to_vec();
+        let mut to = self.to_vec();
+        for pass in 0..T::BYTES {
+            let digit = |x: &T| ((x.radix_key() >> (8 * pass)) & 255) as usize;
+            let mut starts = [0_usize; 257];
+            for x in &from {
+                starts[digit(x) + 1] += 1;
+            }
+            if starts.contains(&from.len()) {
+                continue;
+            }
+            for d in 0..256 {
+                starts[d + 1] += starts[d];
+            }
+            for x in &from {
+                let d = digit(x);
+                to[starts[d]] = *x;
+                starts[d] += 1;
+            }
+            std::mem::swap(&mut from, &mut to);
+        }
+        self.copy_from_slice(&from);
+    }
+    fn counting_sort_by_key<F>(&mut self, bound: usize, key: F)
+        where T: Copy, F: Fn(&T) -> usize {
+        let mut starts = vec![0_usize; bound + 1];
+        for x in self.iter() {
+            starts[key(x) + 1] += 1;
+        }
+        for d in 0..bound {
+            starts[d + 1] += starts[d];
+        }
+        let from = self.to_vec();
+        for x in &from {
+            let d = key(x);
+            self[starts[d]] = *x;
+            starts[d] += 1;
+        }
+    }
+    #[inline]
+    fn argsort(&self) -> Vec<usize> where T: Ord {
+        let mut ans: Vec<usize> = (0..self.len()).collect();
+        ans.sort_by(|&a, &b| self[a].cmp(&self[b]));
+        ans
+    }
+    #[inline]
+    fn argsort_by_key<K, F>(&self, key: F) -> Vec<usize>
+        where K: Ord, F: FnMut(&T) -> K {
+        let keys: Vec<K> = self.iter().map(key).collect();
+        keys.argsort()
+    }
+    fn radix_argsort(&self) -> Vec<usize> where T: RadixKey {
+        let mut pairs: Vec<(T, usize)> = self.iter().cloned().zip(0..).collect();
+        pairs.radix_sort();
+        pairs.iter().map(|&(_, i)| i).collect()
+    }
+}
+
+/// Lexicographic steps over the arrangements of a slice, as `std::next_permutation` in C++.
+///
+/// Both methods return `false` and leave the first arrangement in the given order
+/// when called on the last one, so a `do`-`while` over all arrangements starts from a sorted slice.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::Permutable;
+/// let mut x = [1, 2, 2];
+/// let mut all = vec![x.to_vec()];
+/// while x.next_permutation() {
+///     all.push(x.to_vec());
+/// }
+/// assert_eq!(all, vec![vec![1, 2, 2], vec![2, 1, 2], vec![2, 2, 1]]);
+/// assert_eq!(x, [1, 2, 2]);
+/// assert!(!x.prev_permutation());
+/// assert_eq!(x, [2, 2, 1]);
+/// ```
+pub trait Permutable {
+    fn next_permutation(&mut self) -> bool;
+    fn prev_permutation(&mut self) -> bool;
+}
+
+impl<T: Ord> Permutable for [T] {
+    fn next_permutation(&mut self) -> bool {
+        let pivot = match (1..self.len()).rev().find(|&i| self[i - 1] < self[i]) {
+            Some(i) => i - 1,
+            None => {
+                self.reverse();
+                return false;
+            }
+        };
+        let successor = (pivot + 1..self.len()).rev().find(|&i| self[pivot] < self[i]).unwrap();
+        self.swap(pivot, successor);
+        self[pivot + 1..].reverse();
+        true
+    }
+    fn prev_permutation(&mut self) -> bool {
+        let pivot = match (1..self.len()).rev().find(|&i| self[i - 1] > self[i]) {
+            Some(i) => i - 1,
+            None => {
+                self.reverse();
+                return false;
+            }
+        };
+        let predecessor = (pivot + 1..self.len()).rev().find(|&i| self[pivot] > self[i]).unwrap();
+        self.swap(pivot, predecessor);
+        self[pivot + 1..].reverse();
+        true
+    }
+}
+
+/// Index of the first item for which `pred` is false, given it is true on a prefix of `x`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::partition_point;
+/// assert_eq!(partition_point(&[1, 3, 5, 2], |&v| v % 2 == 1), 3);
+/// assert_eq!(partition_point(&[] as &[i32], |_| true), 0);
+/// ```
+pub fn partition_point<T, F>(x: &[T], mut pred: F) -> usize
+    where F: FnMut(&T) -> bool {
+    if x.is_empty() || !pred(&x[0]) {
         return 0;
     }
     let (mut l, mut r) = (0, x.len());
     while r - l > 1 {
-        let middle = (r + l) / 2;
-        if value < x[middle] {
-            r = middle;
+        let middle = l + (r - l) / 2;
+        if pred(&x[middle]) {
+            l = middle;
         }
         else {
-            l = middle;
+            r = middle;
         }
     }
     r
 }
-fn bisect_left<T>(x: &[T], value: T) -> usize
+
+/// Index of the first item greater than `value` in the sorted `x`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::{bisect_left, bisect_right};
+/// let x = [1, 2, 2, 2, 5];
+/// assert_eq!((bisect_left(&x, 2), bisect_right(&x, 2)), (1, 4));
+/// assert_eq!((bisect_left(&x, 0), bisect_right(&x, 7)), (0, 5));
+/// ```
+#[inline]
+pub fn bisect_right<T>(x: &[T], value: T) -> usize
     where T: PartialOrd {
-    if x.len() == 0 || !(x[0] < value) {
-        return 0;
+    partition_point(x, |item| value.partial_cmp(item) != Some(Ordering::Less))
+}
+/// Index of the first item not less than `value` in the sorted `x`.
+#[inline]
+pub fn bisect_left<T>(x: &[T], value: T) -> usize
+    where T: PartialOrd {
+    partition_point(x, |item| *item < value)
+}
+
+/// `bisect_right` for `x` sorted by `key`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::{bisect_left_by_key, bisect_right_by_key};
+/// let x = [(1, 'a'), (3, 'b'), (3, 'c'), (4, 'd')];
+/// assert_eq!(bisect_left_by_key(&x, &3, |p| p.0), 1);
+/// assert_eq!(bisect_right_by_key(&x, &3, |p| p.0), 3);
+/// ```
+#[inline]
+pub fn bisect_right_by_key<T, K, F>(x: &[T], value: &K, mut key: F) -> usize
+    where K: PartialOrd, F: FnMut(&T) -> K {
+    partition_point(x, |item| value.partial_cmp(&key(item)) != Some(Ordering::Less))
+}
+/// `bisect_left` for `x` sorted by `key`.
+#[inline]
+pub fn bisect_left_by_key<T, K, F>(x: &[T], value: &K, mut key: F) -> usize
+    where K: PartialOrd, F: FnMut(&T) -> K {
+    partition_point(x, |item| key(item) < *value)
+}
+
+/// `bisect_right` with a comparator telling how an item relates to the searched value,
+/// as in `slice::binary_search_by`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::{bisect_left_by, bisect_right_by};
+/// let x = [5, 4, 4, 1];
+/// assert_eq!(bisect_left_by(&x, |item| 4.cmp(item)), 1);
+/// assert_eq!(bisect_right_by(&x, |item| 4.cmp(item)), 3);
+/// ```
+#[inline]
+pub fn bisect_right_by<T, F>(x: &[T], mut cmp: F) -> usize
+    where F: FnMut(&T) -> Ordering {
+    partition_point(x, |item| cmp(item) != Ordering::Greater)
+}
+/// `bisect_left` with a comparator, see `bisect_right_by`.
+#[inline]
+pub fn bisect_left_by<T, F>(x: &[T], mut cmp: F) -> usize
+    where F: FnMut(&T) -> Ordering {
+    partition_point(x, |item| cmp(item) == Ordering::Less)
+}
+
+/// Range of the items equal to `value` in the sorted `x`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::equal_range;
+/// let x = [1, 2, 2, 2, 5];
+/// assert_eq!(equal_range(&x, 2), 1..4);
+/// assert_eq!(equal_range(&x, 3), 4..4);
+/// ```
+#[inline]
+pub fn equal_range<T>(x: &[T], value: T) -> Range<usize>
+    where T: PartialOrd {
+    let left = partition_point(x, |item| *item < value);
+    left..left + partition_point(&x[left..], |item| value.partial_cmp(item) != Some(Ordering::Less))
+}
+/// `equal_range` with a comparator, see `bisect_right_by`.
+#[inline]
+pub fn equal_range_by<T, F>(x: &[T], mut cmp: F) -> Range<usize>
+    where F: FnMut(&T) -> Ordering {
+    let left = bisect_left_by(x, &mut cmp);
+    left..left + bisect_right_by(&x[left..], cmp)
+}
+
+/// Integers which can be binary searched on without overflow at the ends of the type.
+pub trait SearchDomain: Copy + Ord {
+    /// Average rounded down.
+    fn floor_mid(self, other: Self) -> Self;
+    /// Average rounded up.
+    fn ceil_mid(self, other: Self) -> Self;
+    fn succ(self) -> Self;
+    fn pred(self) -> Self;
+}
+
+macro_rules! impl_search_domain {
+    ($($t:ty),*) => ($(
+        impl SearchDomain for $t {
+            #[inline]
+            fn floor_mid(self, other: $t) -> $t {
+                (self & other) + ((self ^ other) >> 1)
+            }
+            #[inline]
+            fn ceil_mid(self, other: $t) -> $t {
+                (self | other) - ((self ^ other) >> 1)
+            }
+            #[inline]
+            fn succ(self) -> $t {
+                self + 1
+            }
+            #[inline]
+            fn pred(self) -> $t {
+                self - 1
+            }
+        }
+    )*);
+}
+
+impl_search_domain!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+/// Smallest `v` in `lo..=hi` with `pred(v)`, given `pred` is false and then true.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::first_true;
+/// assert_eq!(first_true(0_u64, 1 << 30, |v| v * v >= 10), Some(4));
+/// assert_eq!(first_true(i64::MIN, i64::MAX, |v| v >= i64::MAX - 1), Some(i64::MAX - 1));
+/// assert_eq!(first_true(0_u64, u64::MAX, |_| false), None);
+/// assert_eq!(first_true(5, 4, |_| true), None);
+/// ```
+pub fn first_true<T, F>(lo: T, hi: T, mut pred: F) -> Option<T>
+    where T: SearchDomain, F: FnMut(T) -> bool {
+    if lo > hi || !pred(hi) {
+        return None;
     }
-    let (mut l, mut r) = (0, x.len());
-    while r - l > 1 {
-        let middle = (r + l) / 2;
-        if x[middle] < value {
+    let (mut l, mut r) = (lo, hi);
+    while l < r {
+        let middle = l.floor_mid(r);
+        if pred(middle) {
+            r = middle;
+        }
+        else {
+            l = middle.succ();
+        }
+    }
+    Some(l)
+}
+
+/// Largest `v` in `lo..=hi` with `pred(v)`, given `pred` is true and then false.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::last_true;
+/// assert_eq!(last_true(0_u64, 1 << 30, |v| v * v <= 10), Some(3));
+/// assert_eq!(last_true(i64::MIN, i64::MAX, |v| v <= i64::MIN), Some(i64::MIN));
+/// assert_eq!(last_true(0_u64, u64::MAX, |_| true), Some(u64::MAX));
+/// ```
+pub fn last_true<T, F>(lo: T, hi: T, mut pred: F) -> Option<T>
+    where T: SearchDomain, F: FnMut(T) -> bool {
+    if lo > hi || !pred(lo) {
+        return None;
+    }
+    let (mut l, mut r) = (lo, hi);
+    while l < r {
+        let middle = l.ceil_mid(r);
+        if pred(middle) {
             l = middle;
         }
         else {
+            r = middle.pred();
+        }
+    }
+    Some(l)
+}
+
+/// Boundary in `lo..hi` where `pred` turns from false to true, after halving the range `iterations` times.
+///
+/// The returned point is the upper end of the final range, so `pred` holds there
+/// whenever it holds at `hi`. The ends must be finite. After `k` iterations the error is
+/// at most `(hi - lo) / 2^k`, or the spacing of `f64` near the answer if that is larger:
+/// 100 iterations give `1e-12` for ranges up to `10^18`, the whole `f64` range needs about 1100.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::{first_true_f64, first_true_f64_eps};
+/// let sqrt2 = first_true_f64(0.0, 2.0, 100, |v| v * v >= 2.0);
+/// assert!((sqrt2 - 2_f64.sqrt()).abs() < 1e-15);
+/// assert_eq!(first_true_f64(-f64::MAX, f64::MAX, 1100, |v| v >= 1.5), 1.5);
+/// let cbrt3 = first_true_f64_eps(0.0, 3.0, 1e-9, |v| v * v * v >= 3.0);
+/// assert!((cbrt3 - 3_f64.cbrt()).abs() < 1e-9);
+/// ```
+pub fn first_true_f64<F>(lo: f64, hi: f64, iterations: usize, mut pred: F) -> f64
+    where F: FnMut(f64) -> bool {
+    let (mut l, mut r) = (lo, hi);
+    for _ in 0..iterations {
+        // Halves first, as r - l overflows for ends of opposite signs
+        let middle = l / 2.0 + r / 2.0;
+        if pred(middle) {
+            r = middle;
+        }
+        else {
+            l = middle;
+        }
+    }
+    r
+}
+
+/// `first_true_f64` which stops once the range is shorter than `eps`, or cannot be halved any more.
+pub fn first_true_f64_eps<F>(lo: f64, hi: f64, eps: f64, mut pred: F) -> f64
+    where F: FnMut(f64) -> bool {
+    let (mut l, mut r) = (lo, hi);
+    while r - l > eps {
+        let middle = l / 2.0 + r / 2.0;
+        if middle <= l || middle >= r {
+            break;
+        }
+        if pred(middle) {
             r = middle;
         }
+        else {
+            l = middle;
+        }
     }
     r
-}
\ No newline at end of file
+}
+
+/// Argmin of `f` on `lo..=hi` and the minimum, given `f` decreases and then increases.
+///
+/// Equal values are allowed only around the minimum, the leftmost argmin is returned then.
+/// Uses `2 log(hi - lo)` evaluations of `f`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::{ternary_max, ternary_min};
+/// assert_eq!(ternary_min(-100_i64, 100, |x| (x - 7) * (x - 7)), (7, 0));
+/// assert_eq!(ternary_min(0_i64, 100, |x| (x - 50).abs().max(3)), (47, 3));
+/// assert_eq!(ternary_min(0_u64, 10, |x| x), (0, 0));
+/// assert_eq!(ternary_max(i64::MIN, i64::MAX, |x| -(x as i128 - 5).abs()), (5, 0));
+/// ```
+pub fn ternary_min<T, V, F>(lo: T, hi: T, mut f: F) -> (T, V)
+    where T: SearchDomain, V: PartialOrd, F: FnMut(T) -> V {
+    assert!(lo <= hi, "Empty search range");
+    let argmin = if lo == hi {
+        hi
+    } else {
+        first_true(lo, hi.pred(), |m| f(m) <= f(m.succ())).unwrap_or(hi)
+    };
+    (argmin, f(argmin))
+}
+
+/// Argmax of `f` on `lo..=hi` and the maximum, see `ternary_min`.
+pub fn ternary_max<T, V, F>(lo: T, hi: T, mut f: F) -> (T, V)
+    where T: SearchDomain, V: PartialOrd, F: FnMut(T) -> V {
+    assert!(lo <= hi, "Empty search range");
+    let argmax = if lo == hi {
+        hi
+    } else {
+        first_true(lo, hi.pred(), |m| f(m) >= f(m.succ())).unwrap_or(hi)
+    };
+    (argmax, f(argmax))
+}
+
+/// Argmin of a unimodal `f` on `lo..hi` and the minimum, found by golden-section search.
+///
+/// Every iteration shrinks the range by 0.618 with one evaluation of `f`,
+/// so 100 iterations are enough for ranges up to `1e9` and precision `1e-11`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use olympic_lib::shared::slices_extensions::{golden_max, golden_min};
+/// let (x, y) = golden_min(-10.0, 10.0, 100, |x| (x - 1.5) * (x - 1.5) + 2.0);
+/// assert!((x - 1.5).abs() < 1e-7 && (y - 2.0).abs() < 1e-12);
+/// let (x, y) = golden_max(0.0, 3.0, 100, f64::sin);
+/// assert!((x - std::f64::consts::FRAC_PI_2).abs() < 1e-7 && (y - 1.0).abs() < 1e-12);
+/// ```
+pub fn golden_min<F>(lo: f64, hi: f64, iterations: usize, mut f: F) -> (f64, f64)
+    where F: FnMut(f64) -> f64 {
+    let ratio = (5_f64.sqrt() - 1.0) / 2.0;
+    let (mut l, mut r) = (lo, hi);
+    let mut m1 = r - ratio * (r - l);
+    let mut m2 = l + ratio * (r - l);
+    let (mut f1, mut f2) = (f(m1), f(m2));
+    for _ in 0..iterations {
+        if f1 < f2 {
+            r = m2;
+            m2 = m1;
+            f2 = f1;
+            m1 = r - ratio * (r - l);
+            f1 = f(m1);
+        }
+        else {
+            l = m1;
+            m1 = m2;
+            f1 = f2;
+            m2 = l + ratio * (r - l);
+            f2 = f(m2);
+        }
+    }
+    if f1 < f2 { (m1, f1) } else { (m2, f2) }
+}
+
+/// Argmax of a unimodal `f` on `lo..hi` and the maximum, see `golden_min`.
+#[inline]
+pub fn golden_max<F>(lo: f64, hi: f64, iterations: usize, mut f: F) -> (f64, f64)
+    where F: FnMut(f64) -> f64 {
+    let (x, y) = golden_min(lo, hi, iterations, |x| -f(x));
+    (x, -y)
+}
@@ -1,5 +1,10 @@
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::ops::{Index, IndexMut, Range};
+use std::str::FromStr;
+use std::string::ParseError;
+
 #[derive(Clone, Default, Eq, Hash, PartialOrd, Ord, PartialEq)]
-struct MutableString {
+pub struct MutableString {
     vec: Vec<char>
 }
 
@@ -17,7 +22,7 @@ impl MutableString {
 
     #[inline]
     pub fn from_vec(vec: Vec<char>) -> MutableString {
-        MutableString { vec: vec }
+        MutableString { vec }
     }
 
     #[inline]
@@ -68,7 +73,7 @@ impl MutableString {
     }
 
     #[inline]
-    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<char> {
+    pub fn as_mut_vec(&mut self) -> &mut Vec<char> {
         &mut self.vec
     }
 
@@ -88,8 +93,7 @@ impl MutableString {
     }
 
     pub fn into_boxed_str(self) -> Box<str> {
-        let slice = self.vec.into_boxed_slice();
-        unsafe { std::mem::transmute::<Box<[char]>, Box<str>>(slice) }
+        self.vec.into_iter().collect::<String>().into_boxed_str()
     }
 }
 